@@ -215,6 +215,8 @@ pub fn op_webgpu_command_encoder_begin_compute_pass(
 
     let descriptor = wgpu_core::command::ComputePassDescriptor {
         label: args.label.map(Cow::from),
+        max_dispatch_count: None,
+        batch_barriers: false,
     };
 
     let compute_pass =
@@ -88,7 +88,9 @@ impl From<DeviceError> for WebGpuError {
         match err {
             DeviceError::Lost => WebGpuError::Lost,
             DeviceError::OutOfMemory => WebGpuError::OutOfMemory,
-            DeviceError::Invalid => WebGpuError::Validation(err.to_string()),
+            DeviceError::Invalid | DeviceError::ResourceCreationFailed => {
+                WebGpuError::Validation(err.to_string())
+            }
         }
     }
 }
@@ -429,6 +429,7 @@ pub async fn op_webgpu_request_device(
         label: args.label.map(Cow::from),
         features: args.required_features.map(Into::into).unwrap_or_default(),
         limits: args.required_limits.map(Into::into).unwrap_or_default(),
+        zero_buffer_size: None,
     };
 
     let (device, maybe_err) = gfx_select!(adapter => instance.adapter_request_device(
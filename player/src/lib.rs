@@ -80,12 +80,46 @@ impl GlobalPlay for wgc::hub::Global<IdentityPassThroughFactory> {
                 trace::Command::ClearBuffer { dst, offset, size } => self
                     .command_encoder_clear_buffer::<A>(encoder, dst, offset, size)
                     .unwrap(),
+                trace::Command::FillBuffer {
+                    dst,
+                    offset,
+                    size,
+                    value,
+                } => self
+                    .command_encoder_fill_buffer::<A>(encoder, dst, offset, size, value)
+                    .unwrap(),
                 trace::Command::ClearTexture {
                     dst,
                     subresource_range,
                 } => self
                     .command_encoder_clear_texture::<A>(encoder, dst, &subresource_range)
                     .unwrap(),
+                trace::Command::ClearTextureWithValue {
+                    dst,
+                    subresource_range,
+                    value,
+                } => self
+                    .command_encoder_clear_texture_with_value::<A>(
+                        encoder,
+                        dst,
+                        &subresource_range,
+                        value,
+                    )
+                    .unwrap(),
+                trace::Command::ClearDepthTexture {
+                    dst,
+                    subresource_range,
+                    depth,
+                    stencil,
+                } => self
+                    .command_encoder_clear_depth_texture::<A>(
+                        encoder,
+                        dst,
+                        &subresource_range,
+                        depth,
+                        stencil,
+                    )
+                    .unwrap(),
                 trace::Command::WriteTimestamp {
                     query_set_id,
                     query_index,
@@ -108,9 +142,18 @@ impl GlobalPlay for wgc::hub::Global<IdentityPassThroughFactory> {
                         destination_offset,
                     )
                     .unwrap(),
-                trace::Command::RunComputePass { base } => {
-                    self.command_encoder_run_compute_pass_impl::<A>(encoder, base.as_ref())
-                        .unwrap();
+                trace::Command::RunComputePass {
+                    base,
+                    max_dispatch_count,
+                    batch_barriers,
+                } => {
+                    self.command_encoder_run_compute_pass_impl::<A>(
+                        encoder,
+                        base.as_ref(),
+                        max_dispatch_count,
+                        batch_barriers,
+                    )
+                    .unwrap();
                 }
                 trace::Command::RunRenderPass {
                     base,
@@ -91,6 +91,7 @@ impl Test<'_> {
                 label: None,
                 features: self.features | wgt::Features::MAPPABLE_PRIMARY_BUFFERS,
                 limits: wgt::Limits::default(),
+                zero_buffer_size: None,
             },
             None,
             device
@@ -262,6 +262,17 @@ impl PerStageBindingTypeCounter {
         self.compute = self.compute.max(other.compute);
     }
 
+    fn sum(&mut self, other: &Self) {
+        self.vertex += other.vertex;
+        self.fragment += other.fragment;
+        self.compute += other.compute;
+    }
+
+    /// Count of all stages of a render or compute pipeline combined.
+    fn max_per_pipeline(&self) -> u32 {
+        (self.vertex + self.fragment).max(self.compute)
+    }
+
     pub(crate) fn validate(
         &self,
         limit: u32,
@@ -290,6 +301,10 @@ pub(crate) struct BindingTypeMaxCountValidator {
     storage_buffers: PerStageBindingTypeCounter,
     storage_textures: PerStageBindingTypeCounter,
     uniform_buffers: PerStageBindingTypeCounter,
+    /// Storage buffers of all merged bind group layouts, per stage.
+    total_storage_buffers: PerStageBindingTypeCounter,
+    /// Storage textures of all merged bind group layouts, per stage.
+    total_storage_textures: PerStageBindingTypeCounter,
 }
 
 impl BindingTypeMaxCountValidator {
@@ -312,6 +327,7 @@ impl BindingTypeMaxCountValidator {
                 ..
             } => {
                 self.storage_buffers.add(binding.visibility, count);
+                self.total_storage_buffers.add(binding.visibility, count);
                 if has_dynamic_offset {
                     self.dynamic_storage_buffers += count;
                 }
@@ -324,6 +340,7 @@ impl BindingTypeMaxCountValidator {
             }
             wgt::BindingType::StorageTexture { .. } => {
                 self.storage_textures.add(binding.visibility, count);
+                self.total_storage_textures.add(binding.visibility, count);
             }
         }
     }
@@ -336,9 +353,16 @@ impl BindingTypeMaxCountValidator {
         self.storage_buffers.merge(&other.storage_buffers);
         self.storage_textures.merge(&other.storage_textures);
         self.uniform_buffers.merge(&other.uniform_buffers);
+        self.total_storage_buffers.sum(&other.total_storage_buffers);
+        self.total_storage_textures
+            .sum(&other.total_storage_textures);
     }
 
-    pub(crate) fn validate(&self, limits: &wgt::Limits) -> Result<(), BindingTypeMaxCountError> {
+    pub(crate) fn validate(
+        &self,
+        limits: &wgt::Limits,
+        downlevel_limits: &wgt::DownlevelLimits,
+    ) -> Result<(), BindingTypeMaxCountError> {
         if limits.max_dynamic_uniform_buffers_per_pipeline_layout < self.dynamic_uniform_buffers {
             return Err(BindingTypeMaxCountError {
                 kind: BindingTypeMaxCountErrorKind::DynamicUniformBuffers,
@@ -379,6 +403,24 @@ impl BindingTypeMaxCountValidator {
             limits.max_uniform_buffers_per_shader_stage,
             BindingTypeMaxCountErrorKind::UniformBuffers,
         )?;
+        let storage_buffers = self.total_storage_buffers.max_per_pipeline();
+        if downlevel_limits.max_storage_buffers_per_pipeline < storage_buffers {
+            return Err(BindingTypeMaxCountError {
+                kind: BindingTypeMaxCountErrorKind::StorageBuffers,
+                zone: BindingZone::Pipeline,
+                limit: downlevel_limits.max_storage_buffers_per_pipeline,
+                count: storage_buffers,
+            });
+        }
+        let storage_textures = self.total_storage_textures.max_per_pipeline();
+        if downlevel_limits.max_storage_textures_per_pipeline < storage_textures {
+            return Err(BindingTypeMaxCountError {
+                kind: BindingTypeMaxCountErrorKind::StorageTextures,
+                zone: BindingZone::Pipeline,
+                limit: downlevel_limits.max_storage_textures_per_pipeline,
+                count: storage_textures,
+            });
+        }
         Ok(())
     }
 }
@@ -525,6 +567,16 @@ pub enum PushConstantUploadError {
     },
     #[error("provided push constant offset {0} does not respect `PUSH_CONSTANT_ALIGNMENT`")]
     Unaligned(u32),
+    #[error("provided push constant size {0} does not respect `PUSH_CONSTANT_ALIGNMENT`")]
+    UnalignedSize(u32),
+    #[error("provided push constant with offset {offset} and size {size_bytes} overflows the push constant indices")]
+    Overflow { offset: u32, size_bytes: u32 },
+    #[error("provided push constant values {start}..{end} overrun the {available} values recorded in the pass")]
+    ValuesOverrun {
+        start: usize,
+        end: usize,
+        available: usize,
+    },
 }
 
 /// Describes a pipeline layout.
@@ -571,6 +623,10 @@ impl<A: hal::Api> PipelineLayout<A> {
         if offset % wgt::PUSH_CONSTANT_ALIGNMENT != 0 {
             return Err(PushConstantUploadError::Unaligned(offset));
         }
+        let size_bytes = end_offset - offset;
+        if size_bytes % wgt::PUSH_CONSTANT_ALIGNMENT != 0 {
+            return Err(PushConstantUploadError::UnalignedSize(size_bytes));
+        }
 
         // Push constant validation looks very complicated on the surface, but
         // the problem can be range-reduced pretty well.
@@ -48,7 +48,7 @@ mod compat {
             }
         }
 
-        fn make_range(&self, start_index: usize) -> Range<usize> {
+        pub fn make_range(&self, start_index: usize) -> Range<usize> {
             // find first incompatible entry
             let end = self
                 .entries
@@ -89,6 +89,12 @@ mod compat {
                 .filter_map(|(i, e)| if e.is_active() { Some(i) } else { None })
         }
 
+        /// Returns the expected and the assigned value of the entry at `index`.
+        pub fn entry(&self, index: usize) -> (Option<T>, Option<T>) {
+            let entry = &self.entries[index];
+            (entry.expected, entry.assigned)
+        }
+
         pub fn invalid_mask(&self) -> super::BindGroupMask {
             self.entries.iter().enumerate().fold(0, |mask, (i, entry)| {
                 if entry.is_valid() {
@@ -128,6 +134,8 @@ mod compat {
         assert_eq!(man.assign(1, 6), 1..1);
         // finally, bind everything
         assert_eq!(man.assign(0, 4), 0..3);
+        assert_eq!(man.entry(1), (Some(6), Some(6)));
+        assert_eq!(man.entry(3), (None, None));
     }
 }
 
@@ -183,6 +191,18 @@ impl Binder {
         (bind_range.start, &self.payloads[bind_range])
     }
 
+    /// Returns `true` if `bind_group_id` is already assigned to `index` with the same dynamic offsets.
+    pub(super) fn is_group_assigned(
+        &self,
+        index: usize,
+        bind_group_id: Valid<BindGroupId>,
+        offsets: &[wgt::DynamicOffset],
+    ) -> bool {
+        let payload = &self.payloads[index];
+        payload.group_id.as_ref().map(|stored| stored.value) == Some(bind_group_id)
+            && payload.dynamic_offsets == offsets
+    }
+
     pub(super) fn assign_group<'a, A: HalApi>(
         &'a mut self,
         index: usize,
@@ -205,6 +225,11 @@ impl Binder {
         &self.payloads[bind_range]
     }
 
+    /// Returns the bind groups that are set on the raw encoder, starting at index 0.
+    pub(super) fn bound_groups(&self) -> &[EntryPayload] {
+        &self.payloads[self.manager.make_range(0)]
+    }
+
     pub(super) fn list_active(&self) -> impl Iterator<Item = Valid<BindGroupId>> + '_ {
         let payloads = &self.payloads;
         self.manager
@@ -215,6 +240,16 @@ impl Binder {
     pub(super) fn invalid_mask(&self) -> BindGroupMask {
         self.manager.invalid_mask()
     }
+
+    /// Returns the bind group layout the pipeline layout expects at `index`,
+    /// and the layout of the bind group assigned there.
+    pub(super) fn group_layouts(
+        &self,
+        index: usize,
+    ) -> (Option<BindGroupLayoutId>, Option<BindGroupLayoutId>) {
+        let (expected, assigned) = self.manager.entry(index);
+        (expected.map(|id| id.0), assigned.map(|id| id.0))
+    }
 }
 
 struct PushConstantChange {
@@ -517,6 +517,8 @@ impl RenderBundleEncoder {
                 dynamic_offsets: state.flat_dynamic_offsets,
                 string_data: Vec::new(),
                 push_constant_data: Vec::new(),
+                dispatch_groups: Vec::new(),
+                bind_groups: Vec::new(),
             },
             is_ds_read_only: self.is_ds_read_only,
             device_id: Stored {
@@ -622,7 +624,7 @@ impl RenderBundle {
         let mut offsets = self.base.dynamic_offsets.as_slice();
         let mut pipeline_layout_id = None::<id::Valid<id::PipelineLayoutId>>;
         if let Some(ref label) = self.base.label {
-            raw.begin_debug_marker(label);
+            raw.begin_debug_marker(label, 0);
         }
 
         for command in self.base.commands.iter() {
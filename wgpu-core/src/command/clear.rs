@@ -5,10 +5,12 @@ use crate::device::trace::Command as TraceCommand;
 use crate::{
     align_to,
     command::CommandBuffer,
+    device::Device,
     get_lowest_common_denom,
-    hub::{Global, GlobalIdentityHandlerFactory, HalApi, Token},
+    hub::{Global, GlobalIdentityHandlerFactory, HalApi, Storage, Token},
     id::{BufferId, CommandEncoderId, DeviceId, TextureId},
     init_tracker::MemoryInitKind,
+    resource::Texture,
     track::TextureSelector,
 };
 
@@ -43,14 +45,27 @@ pub enum ClearError {
     },
     #[error("destination buffer/texture is missing the `COPY_DST` usage flag")]
     MissingCopyDstUsageFlag(Option<BufferId>, Option<TextureId>),
+    #[error("buffer {0:?} needs the `COPY_DST` or `STORAGE` usage flag to be cleared")]
+    MissingBufferWritableUsageFlag(BufferId),
     #[error("texture lacks the aspects that were specified in the image subresource range. Texture with format {texture_format:?}, specified was {subresource_range_aspects:?}")]
     MissingTextureAspect {
         texture_format: wgt::TextureFormat,
         subresource_range_aspects: TextureAspect,
     },
-    #[error("Depth/Stencil formats are not supported for clearing")]
+    #[error("Depth/Stencil textures not created by wgpu are not supported for clearing")]
     DepthStencilFormatNotSupported,
-    #[error("Multisampled textures are not supported for clearing")]
+    #[error("Depth/Stencil textures can't be cleared to a color")]
+    ColorValueForDepthStencil,
+    #[error("Color textures can't be cleared to a depth/stencil value")]
+    DepthStencilValueForColor,
+    #[error("clear value {value:?} can't be represented in texture format {format:?}")]
+    InvalidClearValue {
+        format: wgt::TextureFormat,
+        value: RenderPassClearValue,
+    },
+    #[error("texture {0:?} needs the `RENDER_ATTACHMENT` usage and a 2D dimension to be cleared to a value")]
+    MissingRenderAttachmentUsageFlag(TextureId),
+    #[error("Multisampled textures not created by wgpu are not supported for clearing")]
     MultisampledTextureUnsupported,
     #[error("image subresource level range is outside of the texture's level range. texture range is {texture_level_range:?},  \
 whereas subesource range specified start {subresource_base_mip_level} and count {subresource_mip_level_count:?}")]
@@ -66,9 +81,18 @@ whereas subesource range specified start {subresource_base_array_layer} and coun
         subresource_base_array_layer: u32,
         subresource_array_layer_count: Option<NonZeroU32>,
     },
+    #[error("clear {index} of the batch is invalid")]
+    InvalidBatchEntry {
+        index: usize,
+        #[source]
+        source: Box<ClearError>,
+    },
 }
 
 impl<G: GlobalIdentityHandlerFactory> Global<G> {
+    /// Clears the buffer range to zero.
+    ///
+    /// The buffer needs either the `COPY_DST` or the `STORAGE` usage.
     pub fn command_encoder_clear_buffer<A: HalApi>(
         &self,
         command_encoder_id: CommandEncoderId,
@@ -77,7 +101,31 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         size: Option<BufferSize>,
     ) -> Result<(), ClearError> {
         profiling::scope!("CommandEncoder::clear_buffer");
+        self.fill_buffer_impl::<A>(command_encoder_id, dst, offset, size, None)
+    }
 
+    /// Fills the buffer range with the 4-byte `value` repeated, instead of zero.
+    pub fn command_encoder_fill_buffer<A: HalApi>(
+        &self,
+        command_encoder_id: CommandEncoderId,
+        dst: BufferId,
+        offset: BufferAddress,
+        size: Option<BufferSize>,
+        value: u32,
+    ) -> Result<(), ClearError> {
+        profiling::scope!("CommandEncoder::fill_buffer");
+        self.fill_buffer_impl::<A>(command_encoder_id, dst, offset, size, Some(value))
+    }
+
+    /// Fills the buffer range with `value`, or with zero if there is none.
+    fn fill_buffer_impl<A: HalApi>(
+        &self,
+        command_encoder_id: CommandEncoderId,
+        dst: BufferId,
+        offset: BufferAddress,
+        size: Option<BufferSize>,
+        value: Option<u32>,
+    ) -> Result<(), ClearError> {
         let hub = A::hub(self);
         let mut token = Token::root();
         let (mut cmd_buf_guard, mut token) = hub.command_buffers.write(&mut token);
@@ -87,7 +135,15 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
 
         #[cfg(feature = "trace")]
         if let Some(ref mut list) = cmd_buf.commands {
-            list.push(TraceCommand::ClearBuffer { dst, offset, size });
+            list.push(match value {
+                None => TraceCommand::ClearBuffer { dst, offset, size },
+                Some(value) => TraceCommand::FillBuffer {
+                    dst,
+                    offset,
+                    size,
+                    value,
+                },
+            });
         }
 
         if !cmd_buf.support_clear_buffer_texture {
@@ -103,8 +159,12 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             .raw
             .as_ref()
             .ok_or(ClearError::InvalidBuffer(dst))?;
-        if !dst_buffer.usage.contains(BufferUsages::COPY_DST) {
-            return Err(ClearError::MissingCopyDstUsageFlag(Some(dst), None));
+        // Raw buffers with either usage support `COPY_DST`, see `Device::create_buffer`.
+        if !dst_buffer
+            .usage
+            .intersects(BufferUsages::COPY_DST | BufferUsages::STORAGE)
+        {
+            return Err(ClearError::MissingBufferWritableUsageFlag(dst));
         }
 
         // Check if offset & size are valid.
@@ -143,11 +203,21 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 MemoryInitKind::ImplicitlyInitialized,
             ));
         // actual hal barrier & operation
-        let dst_barrier = dst_pending.map(|pending| pending.into_hal(dst_buffer));
+        let mut dst_barrier = dst_pending
+            .map(|pending| pending.into_hal(dst_buffer))
+            .peekable();
         let cmd_buf_raw = cmd_buf.encoder.open();
         unsafe {
-            cmd_buf_raw.transition_buffers(dst_barrier);
-            cmd_buf_raw.clear_buffer(dst_raw, offset..end);
+            // No barrier is needed if the buffer wasn't used in this command buffer yet,
+            // or was last used as a copy destination. The transition from the device
+            // state is then inserted at submission.
+            if dst_barrier.peek().is_some() {
+                cmd_buf_raw.transition_buffers(dst_barrier);
+            }
+            match value {
+                None => cmd_buf_raw.clear_buffer(dst_raw, offset..end),
+                Some(value) => cmd_buf_raw.fill_buffer(dst_raw, offset..end, value),
+            }
         }
         Ok(())
     }
@@ -159,34 +229,284 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         subresource_range: &ImageSubresourceRange,
     ) -> Result<(), ClearError> {
         profiling::scope!("CommandEncoder::clear_texture");
+        self.clear_texture_impl::<A>(
+            command_encoder_id,
+            dst,
+            std::slice::from_ref(subresource_range),
+            None,
+        )
+    }
+
+    /// Clears a color texture to `value` instead of zero.
+    ///
+    /// This is done with a render pass, so the texture needs to be a 2D texture
+    /// with the `RENDER_ATTACHMENT` usage.
+    pub fn command_encoder_clear_texture_with_value<A: HalApi>(
+        &self,
+        command_encoder_id: CommandEncoderId,
+        dst: TextureId,
+        subresource_range: &ImageSubresourceRange,
+        value: wgt::Color,
+    ) -> Result<(), ClearError> {
+        profiling::scope!("CommandEncoder::clear_texture_with_value");
+        self.clear_texture_impl::<A>(
+            command_encoder_id,
+            dst,
+            std::slice::from_ref(subresource_range),
+            Some(RenderPassClearValue::Color(value)),
+        )
+    }
+
+    /// Clears a depth/stencil texture to `depth`, and to `stencil` if there is one.
+    ///
+    /// If `stencil` is `None`, the stencil aspect is left untouched.
+    pub fn command_encoder_clear_depth_texture<A: HalApi>(
+        &self,
+        command_encoder_id: CommandEncoderId,
+        dst: TextureId,
+        subresource_range: &ImageSubresourceRange,
+        depth: f32,
+        stencil: Option<u32>,
+    ) -> Result<(), ClearError> {
+        profiling::scope!("CommandEncoder::clear_depth_texture");
+        self.clear_texture_impl::<A>(
+            command_encoder_id,
+            dst,
+            std::slice::from_ref(subresource_range),
+            Some(RenderPassClearValue::DepthStencil(depth, stencil)),
+        )
+    }
+
+    /// Clears several subresource ranges of one texture at once.
+    ///
+    /// All ranges are validated before anything is recorded, and the texture
+    /// is transitioned with a single barrier covering all of them.
+    pub fn command_encoder_clear_texture_ranges<A: HalApi>(
+        &self,
+        command_encoder_id: CommandEncoderId,
+        dst: TextureId,
+        subresource_ranges: &[ImageSubresourceRange],
+    ) -> Result<(), ClearError> {
+        profiling::scope!("CommandEncoder::clear_texture_ranges");
+        self.clear_texture_impl::<A>(command_encoder_id, dst, subresource_ranges, None)
+    }
+
+    /// Returns the extent clearing `mip_level` of the texture writes to.
+    ///
+    /// This is the size of the mip level rounded up to whole blocks of the
+    /// texture format, so it can exceed the size of the mip level.
+    pub fn texture_mip_clear_extent<A: HalApi>(
+        &self,
+        texture_id: TextureId,
+        mip_level: u32,
+    ) -> Result<wgt::Extent3d, ClearError> {
+        let hub = A::hub(self);
+        let mut token = Token::root();
+        let (texture_guard, _) = hub.textures.read(&mut token);
+        let texture = texture_guard
+            .get(texture_id)
+            .map_err(|_| ClearError::InvalidTexture(texture_id))?;
+
+        clear_mip_size(&texture.desc, mip_level).ok_or_else(|| {
+            ClearError::InvalidTextureLevelRange {
+                texture_level_range: texture.full_range.levels.clone(),
+                subresource_base_mip_level: mip_level,
+                subresource_mip_level_count: NonZeroU32::new(1),
+            }
+        })
+    }
+
+    /// Clears several textures to zero at once.
+    ///
+    /// Unlike calling [`Global::command_encoder_clear_texture`] for each entry,
+    /// the guards are only acquired once, all entries are validated before
+    /// anything is recorded and all textures are transitioned with one barrier.
+    pub fn command_encoder_clear_textures<A: HalApi>(
+        &self,
+        command_encoder_id: CommandEncoderId,
+        clears: &[(TextureId, ImageSubresourceRange)],
+    ) -> Result<(), ClearError> {
+        profiling::scope!("CommandEncoder::clear_textures");
 
         let hub = A::hub(self);
         let mut token = Token::root();
         let (device_guard, mut token) = hub.devices.write(&mut token);
         let (mut cmd_buf_guard, mut token) = hub.command_buffers.write(&mut token);
-        let cmd_buf = CommandBuffer::get_encoder_mut(&mut *cmd_buf_guard, command_encoder_id)
-            .map_err(|_| ClearError::InvalidCommandEncoder(command_encoder_id))?;
+        let cmd_buf =
+            CommandBuffer::get_encoder_mut_keep_clears(&mut *cmd_buf_guard, command_encoder_id)
+                .map_err(|_| ClearError::InvalidCommandEncoder(command_encoder_id))?;
         let (_, mut token) = hub.buffers.read(&mut token); // skip token
         let (texture_guard, _) = hub.textures.read(&mut token);
 
+        // Recorded as separate clears, which replay identically.
         #[cfg(feature = "trace")]
         if let Some(ref mut list) = cmd_buf.commands {
-            list.push(TraceCommand::ClearTexture {
-                dst,
-                subresource_range: subresource_range.clone(),
-            });
+            for &(dst, ref subresource_range) in clears {
+                list.push(TraceCommand::ClearTexture {
+                    dst,
+                    subresource_range: subresource_range.clone(),
+                });
+            }
         }
 
         if !cmd_buf.support_clear_buffer_texture {
             return Err(ClearError::MissingClearCommandsFeature);
         }
 
-        let dst_texture = texture_guard
-            .get(dst)
-            .map_err(|_| ClearError::InvalidTexture(dst))?;
+        let device = &device_guard[cmd_buf.device_id.value];
+        let prepared_clears = clears
+            .iter()
+            .enumerate()
+            .map(|(index, &(dst, ref subresource_range))| {
+                prepare_texture_clear(
+                    &*texture_guard,
+                    &cmd_buf.recent_texture_clears,
+                    device.srgb_clear_needs_render_pass,
+                    dst,
+                    std::slice::from_ref(subresource_range),
+                    None,
+                )
+                .map_err(|source| ClearError::InvalidBatchEntry {
+                    index,
+                    source: Box::new(source),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        record_texture_clears(cmd_buf, &*texture_guard, device, prepared_clears)
+    }
+
+    /// Clears the texture to `value`, or to zero (respectively depth 1.0 and
+    /// stencil 0 for depth/stencil textures) if there is none.
+    fn clear_texture_impl<A: HalApi>(
+        &self,
+        command_encoder_id: CommandEncoderId,
+        dst: TextureId,
+        subresource_ranges: &[ImageSubresourceRange],
+        value: Option<RenderPassClearValue>,
+    ) -> Result<(), ClearError> {
+        let hub = A::hub(self);
+        let mut token = Token::root();
+        let (device_guard, mut token) = hub.devices.write(&mut token);
+        let (mut cmd_buf_guard, mut token) = hub.command_buffers.write(&mut token);
+        let cmd_buf =
+            CommandBuffer::get_encoder_mut_keep_clears(&mut *cmd_buf_guard, command_encoder_id)
+                .map_err(|_| ClearError::InvalidCommandEncoder(command_encoder_id))?;
+        let (_, mut token) = hub.buffers.read(&mut token); // skip token
+        let (texture_guard, _) = hub.textures.read(&mut token);
+
+        // Multiple ranges are recorded as separate clears, which replay identically.
+        #[cfg(feature = "trace")]
+        if let Some(ref mut list) = cmd_buf.commands {
+            for subresource_range in subresource_ranges {
+                list.push(match value {
+                    None => TraceCommand::ClearTexture {
+                        dst,
+                        subresource_range: subresource_range.clone(),
+                    },
+                    Some(RenderPassClearValue::Color(value)) => {
+                        TraceCommand::ClearTextureWithValue {
+                            dst,
+                            subresource_range: subresource_range.clone(),
+                            value,
+                        }
+                    }
+                    Some(RenderPassClearValue::DepthStencil(depth, stencil)) => {
+                        TraceCommand::ClearDepthTexture {
+                            dst,
+                            subresource_range: subresource_range.clone(),
+                            depth,
+                            stencil,
+                        }
+                    }
+                });
+            }
+        }
+
+        if !cmd_buf.support_clear_buffer_texture {
+            return Err(ClearError::MissingClearCommandsFeature);
+        }
+
+        let device = &device_guard[cmd_buf.device_id.value];
+        let prepared_clear = prepare_texture_clear(
+            &*texture_guard,
+            &cmd_buf.recent_texture_clears,
+            device.srgb_clear_needs_render_pass,
+            dst,
+            subresource_ranges,
+            value,
+        )?;
+
+        record_texture_clears(cmd_buf, &*texture_guard, device, vec![prepared_clear])
+    }
+}
+
+/// Subresources of a texture that passed validation for a clear.
+struct PreparedTextureClear {
+    texture: TextureId,
+    /// `None` if the texture is cleared to zero with copies.
+    value: Option<RenderPassClearValue>,
+    ranges: Vec<(hal::FormatAspects, TextureSelector)>,
+}
+
+/// Validates a clear of `subresource_ranges` of the texture without recording anything.
+///
+/// Ranges that were just cleared to the same value are left out.
+fn prepare_texture_clear<A: HalApi>(
+    texture_guard: &Storage<Texture<A>, TextureId>,
+    recent_clears: &[RecentTextureClear],
+    srgb_clear_needs_render_pass: bool,
+    dst: TextureId,
+    subresource_ranges: &[ImageSubresourceRange],
+    value: Option<RenderPassClearValue>,
+) -> Result<PreparedTextureClear, ClearError> {
+    let dst_texture = texture_guard
+        .get(dst)
+        .map_err(|_| ClearError::InvalidTexture(dst))?;
+
+    // Check if texture is supported for clearing
+    let is_depth_stencil =
+        dst_texture.desc.format.describe().sample_type == wgt::TextureSampleType::Depth;
+    // Depth/stencil textures can't be filled from the zero buffer on all backends,
+    // and the zero buffer can't provide other values,
+    // so these are cleared with a render pass instead.
+    let clear_value = match value {
+        Some(value) => {
+            validate_clear_value(dst_texture.desc.format, value)?;
+            Some(value)
+        }
+        None if is_depth_stencil => Some(RenderPassClearValue::DepthStencil(1.0, Some(0))),
+        // Copies can't write to the samples of multisampled textures.
+        None if dst_texture.desc.sample_count > 1 => {
+            Some(RenderPassClearValue::Color(wgt::Color::TRANSPARENT))
+        }
+        // Copies give wrong results for sRGB textures on some drivers.
+        // Without clear views, there is no other way than copying though.
+        None if srgb_clear_needs_render_pass
+            && dst_texture.desc.format.describe().srgb
+            && !dst_texture.clear_views.is_empty() =>
+        {
+            Some(RenderPassClearValue::Color(wgt::Color::TRANSPARENT))
+        }
+        None => None,
+    };
+    if clear_value.is_some() && dst_texture.clear_views.is_empty() {
+        return Err(if is_depth_stencil {
+            ClearError::DepthStencilFormatNotSupported
+        } else if dst_texture.desc.sample_count > 1 {
+            ClearError::MultisampledTextureUnsupported
+        } else {
+            ClearError::MissingRenderAttachmentUsageFlag(dst)
+        });
+    }
 
+    let mut clear_ranges = Vec::with_capacity(subresource_ranges.len());
+    for subresource_range in subresource_ranges {
         // Check if subresource aspects are valid.
-        let requested_aspects = hal::FormatAspects::from(subresource_range.aspect);
+        let mut requested_aspects = hal::FormatAspects::from(subresource_range.aspect);
+        if let Some(RenderPassClearValue::DepthStencil(_, None)) = clear_value {
+            requested_aspects.remove(hal::FormatAspects::STENCIL);
+        }
         let clear_aspects = hal::FormatAspects::from(dst_texture.desc.format) & requested_aspects;
         if clear_aspects.is_empty() {
             return Err(ClearError::MissingTextureAspect {
@@ -195,14 +515,6 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             });
         };
 
-        // Check if texture is supported for clearing
-        if dst_texture.desc.format.describe().sample_type == wgt::TextureSampleType::Depth {
-            return Err(ClearError::DepthStencilFormatNotSupported);
-        }
-        if dst_texture.desc.sample_count > 1 {
-            return Err(ClearError::MultisampledTextureUnsupported);
-        }
-
         // Check if subresource level range is valid
         let subresource_level_end = match subresource_range.mip_level_count {
             Some(count) => subresource_range.base_mip_level + count.get(),
@@ -232,61 +544,351 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             });
         }
 
-        // query from tracker with usage (and check usage)
-        let (dst_texture, dst_pending) = cmd_buf
-            .trackers
-            .textures
-            .use_replace(
-                &*texture_guard,
-                dst,
-                TextureSelector {
-                    levels: subresource_range.base_mip_level..subresource_level_end,
-                    layers: subresource_range.base_array_layer..subresource_layer_end,
-                },
-                hal::TextureUses::COPY_DST,
-            )
-            .map_err(ClearError::InvalidTexture)?;
-        let dst_raw = dst_texture
-            .inner
-            .as_raw()
-            .ok_or(ClearError::InvalidTexture(dst))?;
-        if !dst_texture.desc.usage.contains(TextureUsages::COPY_DST) {
+        clear_ranges.push((
+            clear_aspects,
+            TextureSelector {
+                levels: subresource_range.base_mip_level..subresource_level_end,
+                layers: subresource_range.base_array_layer..subresource_layer_end,
+            },
+        ));
+    }
+
+    // Nothing that could have written to the texture was recorded since the
+    // recent clears, so clearing the exact same range to the same value again is a no-op.
+    clear_ranges.retain(|&(aspects, ref selector)| {
+        !recent_clears.iter().any(|clear| {
+            clear.texture == dst
+                && clear.aspects == aspects
+                && clear.selector == *selector
+                && clear.value == clear_value
+        })
+    });
+    if !clear_ranges.is_empty() {
+        if clear_value.is_none() && !dst_texture.desc.usage.contains(TextureUsages::COPY_DST) {
             return Err(ClearError::MissingCopyDstUsageFlag(None, Some(dst)));
         }
+        if dst_texture.inner.as_raw().is_none() {
+            return Err(ClearError::InvalidTexture(dst));
+        }
+    }
 
-        // actual hal barrier & operation
-        let dst_barrier = dst_pending.map(|pending| pending.into_hal(dst_texture));
-        let encoder = cmd_buf.encoder.open();
-        let device = &device_guard[cmd_buf.device_id.value];
+    Ok(PreparedTextureClear {
+        texture: dst,
+        value: clear_value,
+        ranges: clear_ranges,
+    })
+}
 
-        let mut zero_buffer_copy_regions = Vec::new();
-        collect_zero_buffer_copies_for_clear_texture(
-            &dst_texture.desc,
-            device.alignments.buffer_copy_pitch.get() as u32,
-            subresource_range.base_mip_level..subresource_level_end,
-            subresource_range.base_array_layer..subresource_layer_end,
-            &mut zero_buffer_copy_regions,
-        );
-        unsafe {
-            encoder.transition_textures(dst_barrier);
-            if !zero_buffer_copy_regions.is_empty() {
-                encoder.copy_buffer_to_texture(
-                    &device.zero_buffer,
-                    dst_raw,
-                    zero_buffer_copy_regions.into_iter(),
+/// Records the prepared clears, transitioning all textures with a single barrier.
+///
+/// Zero clears of the same texture are done with a single copy.
+fn record_texture_clears<A: HalApi>(
+    cmd_buf: &mut CommandBuffer<A>,
+    texture_guard: &Storage<Texture<A>, TextureId>,
+    device: &Device<A>,
+    mut clears: Vec<PreparedTextureClear>,
+) -> Result<(), ClearError> {
+    clears.retain(|clear| !clear.ranges.is_empty());
+    if clears.is_empty() {
+        return Ok(());
+    }
+
+    // query from tracker with usage (and check usage)
+    let mut dst_textures = Vec::with_capacity(clears.len());
+    let mut dst_barriers = Vec::new();
+    for clear in clears.iter() {
+        let dst_usage = match clear.value {
+            Some(RenderPassClearValue::DepthStencil(..)) => hal::TextureUses::DEPTH_STENCIL_WRITE,
+            Some(RenderPassClearValue::Color(_)) => hal::TextureUses::COLOR_TARGET,
+            None => hal::TextureUses::COPY_DST,
+        };
+        let dst_texture = texture_guard
+            .get(clear.texture)
+            .map_err(|_| ClearError::InvalidTexture(clear.texture))?;
+        for &(aspects, ref selector) in clear.ranges.iter() {
+            let (_, pending) = cmd_buf
+                .trackers
+                .textures
+                .use_replace(texture_guard, clear.texture, selector.clone(), dst_usage)
+                .map_err(ClearError::InvalidTexture)?;
+            dst_barriers.extend(pending.map(|pending| pending.into_hal(dst_texture)));
+
+            cmd_buf
+                .recent_texture_clears
+                .retain(|recent| !recent.overlaps(clear.texture, aspects, selector));
+            cmd_buf.recent_texture_clears.push(RecentTextureClear {
+                texture: clear.texture,
+                aspects,
+                selector: selector.clone(),
+                value: clear.value,
+            });
+        }
+        dst_textures.push(dst_texture);
+    }
+
+    // actual hal barrier & operation
+    let encoder = cmd_buf.encoder.open();
+    unsafe {
+        encoder.transition_textures(dst_barriers.into_iter());
+    }
+
+    let mut zero_buffer_copies: Vec<(&Texture<A>, Vec<hal::BufferTextureCopy>)> = Vec::new();
+    for (clear, dst_texture) in clears.into_iter().zip(dst_textures) {
+        if let Some(clear_value) = clear.value {
+            for (clear_aspects, selector) in clear.ranges {
+                clear_texture_via_render_pass(
+                    dst_texture,
+                    clear_aspects,
+                    selector.levels,
+                    selector.layers,
+                    clear_value,
+                    encoder,
                 );
             }
+            continue;
         }
-        Ok(())
+
+        let index = match zero_buffer_copies
+            .iter()
+            .position(|&(texture, _)| std::ptr::eq(texture, dst_texture))
+        {
+            Some(index) => index,
+            None => {
+                zero_buffer_copies.push((dst_texture, Vec::new()));
+                zero_buffer_copies.len() - 1
+            }
+        };
+        for (_, selector) in clear.ranges {
+            collect_zero_buffer_copies_for_clear_texture(
+                &dst_texture.desc,
+                device.alignments.buffer_copy_pitch.get() as u32,
+                device.zero_buffer_size,
+                selector.levels,
+                selector.layers,
+                &mut zero_buffer_copies[index].1,
+            );
+        }
+    }
+    for (dst_texture, copy_regions) in zero_buffer_copies {
+        if copy_regions.is_empty() {
+            continue;
+        }
+        // Checked by `prepare_texture_clear`.
+        let dst_raw = dst_texture.inner.as_raw().unwrap();
+        unsafe {
+            encoder.copy_buffer_to_texture(&device.zero_buffer, dst_raw, copy_regions.into_iter());
+        }
+    }
+    Ok(())
+}
+
+/// A texture clear that nothing else was recorded after on its encoder.
+#[derive(Debug)]
+pub(super) struct RecentTextureClear {
+    texture: TextureId,
+    aspects: hal::FormatAspects,
+    selector: TextureSelector,
+    /// `None` if the texture was cleared to zero with copies.
+    value: Option<RenderPassClearValue>,
+}
+
+impl RecentTextureClear {
+    fn overlaps(
+        &self,
+        texture: TextureId,
+        aspects: hal::FormatAspects,
+        selector: &TextureSelector,
+    ) -> bool {
+        self.texture == texture
+            && self.aspects.intersects(aspects)
+            && self.selector.levels.start < selector.levels.end
+            && selector.levels.start < self.selector.levels.end
+            && self.selector.layers.start < selector.layers.end
+            && selector.layers.start < self.selector.layers.end
     }
 }
 
+/// Value a texture is cleared to by [`clear_texture_via_render_pass`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RenderPassClearValue {
+    Color(wgt::Color),
+    /// Depth value, and stencil value if the stencil aspect is cleared as well.
+    DepthStencil(f32, Option<u32>),
+}
+
+/// Checks that `value` matches the kind of `format` and fits into its channels.
+///
+/// Render passes reinterpret the value for the attachment, so an integer format
+/// would otherwise be cleared to a truncated or wrapped-around value.
+fn validate_clear_value(
+    format: wgt::TextureFormat,
+    value: RenderPassClearValue,
+) -> Result<(), ClearError> {
+    let format_desc = format.describe();
+    let is_depth_stencil = format_desc.sample_type == wgt::TextureSampleType::Depth;
+    let invalid = || ClearError::InvalidClearValue { format, value };
+    match value {
+        RenderPassClearValue::Color(_) if is_depth_stencil => {
+            Err(ClearError::ColorValueForDepthStencil)
+        }
+        RenderPassClearValue::DepthStencil(..) if !is_depth_stencil => {
+            Err(ClearError::DepthStencilValueForColor)
+        }
+        RenderPassClearValue::DepthStencil(depth, stencil) => {
+            // Stencil aspects are 8 bits wide in all formats.
+            if (0.0..=1.0).contains(&depth) && stencil.unwrap_or(0) <= 0xFF {
+                Ok(())
+            } else {
+                Err(invalid())
+            }
+        }
+        RenderPassClearValue::Color(color) => {
+            // All integer formats have channels of the same width.
+            let bits = format_desc.block_size as u32 * 8 / format_desc.components as u32;
+            let range = match format_desc.sample_type {
+                wgt::TextureSampleType::Uint => 0.0..=((1u64 << bits) - 1) as f64,
+                wgt::TextureSampleType::Sint => {
+                    -((1u64 << (bits - 1)) as f64)..=((1u64 << (bits - 1)) - 1) as f64
+                }
+                _ => return Ok(()),
+            };
+            let channels = [color.r, color.g, color.b, color.a];
+            if channels[..format_desc.components as usize]
+                .iter()
+                .all(|&c| c.fract() == 0.0 && range.contains(&c))
+            {
+                Ok(())
+            } else {
+                Err(invalid())
+            }
+        }
+    }
+}
+
+/// Clears the given aspects of a texture by running an empty render pass
+/// on each of the selected subresources.
+///
+/// The texture is expected to be in the `COLOR_TARGET` or `DEPTH_STENCIL_WRITE`
+/// state already, matching the kind of `clear_value`.
+fn clear_texture_via_render_pass<A: hal::Api>(
+    texture: &Texture<A>,
+    aspects: hal::FormatAspects,
+    mip_range: Range<u32>,
+    layer_range: Range<u32>,
+    clear_value: RenderPassClearValue,
+    encoder: &mut A::CommandEncoder,
+) {
+    let aspect_ops = |aspect| {
+        if aspects.contains(aspect) {
+            hal::AttachmentOps::STORE
+        } else {
+            hal::AttachmentOps::LOAD | hal::AttachmentOps::STORE
+        }
+    };
+
+    for mip_level in mip_range {
+        let extent = wgt::Extent3d {
+            depth_or_array_layers: 1,
+            ..texture.desc.mip_level_size(mip_level).unwrap()
+        };
+        for array_layer in layer_range.clone() {
+            let view = texture.clear_view(mip_level, array_layer).unwrap();
+            let (color_attachment, depth_stencil_attachment) = match clear_value {
+                RenderPassClearValue::Color(value) => (
+                    Some(hal::ColorAttachment {
+                        target: hal::Attachment {
+                            view,
+                            usage: hal::TextureUses::COLOR_TARGET,
+                        },
+                        resolve_target: None,
+                        ops: hal::AttachmentOps::STORE,
+                        clear_value: value,
+                    }),
+                    None,
+                ),
+                RenderPassClearValue::DepthStencil(depth, stencil) => (
+                    None,
+                    Some(hal::DepthStencilAttachment {
+                        target: hal::Attachment {
+                            view,
+                            usage: hal::TextureUses::DEPTH_STENCIL_WRITE,
+                        },
+                        depth_ops: aspect_ops(hal::FormatAspects::DEPTH),
+                        stencil_ops: aspect_ops(hal::FormatAspects::STENCIL),
+                        clear_value: (depth, stencil.unwrap_or(0)),
+                    }),
+                ),
+            };
+            let desc = hal::RenderPassDescriptor {
+                label: Some("(wgpu internal) clear texture"),
+                extent,
+                sample_count: texture.desc.sample_count,
+                color_attachments: match color_attachment {
+                    Some(ref attachment) => std::slice::from_ref(attachment),
+                    None => &[],
+                },
+                depth_stencil_attachment,
+            };
+            unsafe {
+                encoder.begin_render_pass(&desc);
+                encoder.end_render_pass();
+            }
+        }
+    }
+}
+
+/// Size of the given mip level, rounded up to whole blocks of the texture format.
+///
+/// This is the extent clearing the mip level writes to.
+fn clear_mip_size(
+    texture_desc: &wgt::TextureDescriptor<()>,
+    mip_level: u32,
+) -> Option<wgt::Extent3d> {
+    let format_desc = texture_desc.format.describe();
+    let mut mip_size = texture_desc.mip_level_size(mip_level)?;
+    // Round to multiple of block size
+    mip_size.width = align_to(mip_size.width, format_desc.block_dimensions.0 as u32);
+    mip_size.height = align_to(mip_size.height, format_desc.block_dimensions.1 as u32);
+    Some(mip_size)
+}
+
+/// Maximum number of texel rows written by a single zero buffer copy.
+///
+/// Browsers can stall on large uploads to WebGL textures,
+/// so texture clears are split into smaller copies there.
+#[cfg(target_arch = "wasm32")]
+const MAX_ZERO_BUFFER_COPY_ROWS: u32 = 256;
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_ZERO_BUFFER_COPY_ROWS: u32 = u32::MAX;
+
 pub(crate) fn collect_zero_buffer_copies_for_clear_texture(
     texture_desc: &wgt::TextureDescriptor<()>,
     buffer_copy_pitch: u32,
+    zero_buffer_size: BufferAddress,
     mip_range: Range<u32>,
     layer_range: Range<u32>,
     out_copy_regions: &mut Vec<hal::BufferTextureCopy>, // TODO: Something better than Vec
+) {
+    collect_zero_buffer_copies(
+        texture_desc,
+        buffer_copy_pitch,
+        // Copies are computed in `u32`, a larger buffer wouldn't be used entirely anyway.
+        zero_buffer_size.min(u32::MAX as BufferAddress) as u32,
+        MAX_ZERO_BUFFER_COPY_ROWS,
+        mip_range,
+        layer_range,
+        out_copy_regions,
+    )
+}
+
+fn collect_zero_buffer_copies(
+    texture_desc: &wgt::TextureDescriptor<()>,
+    buffer_copy_pitch: u32,
+    zero_buffer_size: u32,
+    max_rows_per_copy: u32,
+    mip_range: Range<u32>,
+    layer_range: Range<u32>,
+    out_copy_regions: &mut Vec<hal::BufferTextureCopy>,
 ) {
     let format_desc = texture_desc.format.describe();
 
@@ -294,63 +896,290 @@ pub(crate) fn collect_zero_buffer_copies_for_clear_texture(
         get_lowest_common_denom(buffer_copy_pitch, format_desc.block_size as u32);
 
     for mip_level in mip_range {
-        let mut mip_size = texture_desc.mip_level_size(mip_level).unwrap();
-        // Round to multiple of block size
-        mip_size.width = align_to(mip_size.width, format_desc.block_dimensions.0 as u32);
-        mip_size.height = align_to(mip_size.height, format_desc.block_dimensions.1 as u32);
+        let mip_size = clear_mip_size(texture_desc, mip_level).unwrap();
 
         let bytes_per_row = align_to(
             mip_size.width / format_desc.block_dimensions.0 as u32 * format_desc.block_size as u32,
             bytes_per_row_alignment,
         );
 
-        let max_rows_per_copy = crate::device::ZERO_BUFFER_SIZE as u32 / bytes_per_row;
+        let max_rows_per_copy = (zero_buffer_size / bytes_per_row).min(max_rows_per_copy);
         // round down to a multiple of rows needed by the texture format
         let max_rows_per_copy = max_rows_per_copy / format_desc.block_dimensions.1 as u32
             * format_desc.block_dimensions.1 as u32;
-        assert!(max_rows_per_copy > 0, "Zero buffer size is too small to fill a single row of a texture with format {:?} and desc {:?}",
+        // If a single row doesn't fit into the zero buffer, each row is split into multiple copies.
+        let (max_rows_per_copy, max_width_per_copy) = if max_rows_per_copy > 0 {
+            (max_rows_per_copy, mip_size.width)
+        } else {
+            let max_blocks_per_copy = zero_buffer_size / bytes_per_row_alignment
+                * bytes_per_row_alignment
+                / format_desc.block_size as u32;
+            assert!(max_blocks_per_copy > 0, "Zero buffer size is too small to fill a single block of a texture with format {:?} and desc {:?}",
                         texture_desc.format, texture_desc.size);
+            (
+                format_desc.block_dimensions.1 as u32,
+                max_blocks_per_copy * format_desc.block_dimensions.0 as u32,
+            )
+        };
 
-        let z_range = 0..(if texture_desc.dimension == wgt::TextureDimension::D3 {
+        let depth = if texture_desc.dimension == wgt::TextureDimension::D3 {
             mip_size.depth_or_array_layers
         } else {
             1
-        });
+        };
+        // If whole slices of a volume texture fit into the zero buffer,
+        // several of them are cleared with a single copy. The rows of all
+        // slices count towards the row limit of a copy.
+        let max_slices_per_copy = if max_rows_per_copy >= mip_size.height {
+            (zero_buffer_size / (bytes_per_row * mip_size.height))
+                .min(max_rows_per_copy / mip_size.height)
+                .max(1)
+        } else {
+            1
+        };
 
         for array_layer in layer_range.clone() {
-            // TODO: Only doing one layer at a time for volume textures right now.
-            for z in z_range.clone() {
-                // May need multiple copies for each subresource! However, we assume that we never need to split a row.
+            let mut z = 0;
+            while z < depth {
+                let num_slices = (depth - z).min(max_slices_per_copy);
+                // hal expects the image height in rows of blocks.
+                let rows_per_image = if num_slices > 1 {
+                    NonZeroU32::new(mip_size.height / format_desc.block_dimensions.1 as u32)
+                } else {
+                    None
+                };
+
+                // May need multiple copies for each subresource, and even for each row.
                 let mut num_rows_left = mip_size.height;
                 while num_rows_left > 0 {
                     let num_rows = num_rows_left.min(max_rows_per_copy);
 
-                    out_copy_regions.push(hal::BufferTextureCopy {
-                        buffer_layout: wgt::ImageDataLayout {
-                            offset: 0,
-                            bytes_per_row: NonZeroU32::new(bytes_per_row),
-                            rows_per_image: None,
-                        },
-                        texture_base: hal::TextureCopyBase {
-                            mip_level,
-                            array_layer,
-                            origin: wgt::Origin3d {
-                                x: 0, // Always full rows
-                                y: mip_size.height - num_rows_left,
-                                z,
+                    let mut x = 0;
+                    while x < mip_size.width {
+                        let width = (mip_size.width - x).min(max_width_per_copy);
+                        let bytes_per_row = align_to(
+                            width / format_desc.block_dimensions.0 as u32
+                                * format_desc.block_size as u32,
+                            bytes_per_row_alignment,
+                        );
+
+                        out_copy_regions.push(hal::BufferTextureCopy {
+                            buffer_layout: wgt::ImageDataLayout {
+                                offset: 0,
+                                bytes_per_row: NonZeroU32::new(bytes_per_row),
+                                rows_per_image,
                             },
-                            aspect: hal::FormatAspects::all(),
-                        },
-                        size: hal::CopyExtent {
-                            width: mip_size.width, // full row
-                            height: num_rows,
-                            depth: 1, // Only single slice of volume texture at a time right now
-                        },
-                    });
+                            texture_base: hal::TextureCopyBase {
+                                mip_level,
+                                array_layer,
+                                origin: wgt::Origin3d {
+                                    x,
+                                    y: mip_size.height - num_rows_left,
+                                    z,
+                                },
+                                aspect: hal::FormatAspects::all(),
+                            },
+                            size: hal::CopyExtent {
+                                width,
+                                height: num_rows,
+                                depth: num_slices,
+                            },
+                        });
+
+                        x += width;
+                    }
 
                     num_rows_left -= num_rows;
                 }
+
+                z += num_slices;
             }
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{
+        collect_zero_buffer_copies, validate_clear_value, ClearError, RenderPassClearValue,
+    };
+    use std::num::NonZeroU32;
+
+    #[test]
+    fn zero_buffer_copies_split_wide_rows() {
+        let texture_desc = wgt::TextureDescriptor {
+            label: (),
+            size: wgt::Extent3d {
+                width: 8192,
+                height: 2,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgt::TextureDimension::D2,
+            format: wgt::TextureFormat::Rgba32Float,
+            usage: wgt::TextureUsages::COPY_DST,
+        };
+        let mut regions = Vec::new();
+        // A row is 128KiB, so it has to be split in two with a 64KiB zero buffer.
+        collect_zero_buffer_copies(
+            &texture_desc,
+            256,
+            64 << 10,
+            u32::MAX,
+            0..1,
+            0..1,
+            &mut regions,
+        );
+
+        let copies = regions
+            .iter()
+            .map(|region| {
+                (
+                    region.texture_base.origin.x,
+                    region.texture_base.origin.y,
+                    region.size.width,
+                    region.size.height,
+                    region.buffer_layout.bytes_per_row.unwrap().get(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            copies,
+            [
+                (0, 0, 4096, 1, 64 << 10),
+                (4096, 0, 4096, 1, 64 << 10),
+                (0, 1, 4096, 1, 64 << 10),
+                (4096, 1, 4096, 1, 64 << 10),
+            ]
+        );
+    }
+
+    #[test]
+    fn zero_buffer_copies_batch_volume_slices() {
+        let texture_desc = wgt::TextureDescriptor {
+            label: (),
+            size: wgt::Extent3d {
+                width: 64,
+                height: 64,
+                depth_or_array_layers: 64,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgt::TextureDimension::D3,
+            format: wgt::TextureFormat::Rgba8Unorm,
+            usage: wgt::TextureUsages::COPY_DST,
+        };
+        let mut regions = Vec::new();
+        // A slice is 16KiB, so 4 of them fit into a 64KiB zero buffer.
+        collect_zero_buffer_copies(
+            &texture_desc,
+            256,
+            64 << 10,
+            u32::MAX,
+            0..1,
+            0..1,
+            &mut regions,
+        );
+
+        assert_eq!(regions.len(), 16);
+        for (i, region) in regions.iter().enumerate() {
+            assert_eq!(region.texture_base.origin.z, i as u32 * 4);
+            assert_eq!(region.size.depth, 4);
+            assert_eq!(region.size.height, 64);
+            assert_eq!(region.buffer_layout.rows_per_image, NonZeroU32::new(64));
+        }
+    }
+
+    #[test]
+    fn zero_buffer_copies_limit_rows() {
+        let texture_desc = wgt::TextureDescriptor {
+            label: (),
+            size: wgt::Extent3d {
+                width: 64,
+                height: 100,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgt::TextureDimension::D2,
+            format: wgt::TextureFormat::Bc1RgbaUnorm,
+            usage: wgt::TextureUsages::COPY_DST,
+        };
+        let mut regions = Vec::new();
+        // 30 rows are rounded down to 28, a multiple of the block height.
+        collect_zero_buffer_copies(&texture_desc, 256, 64 << 10, 30, 0..1, 0..1, &mut regions);
+
+        let rows = regions
+            .iter()
+            .map(|region| (region.texture_base.origin.y, region.size.height))
+            .collect::<Vec<_>>();
+        assert_eq!(rows, [(0, 28), (28, 28), (56, 28), (84, 16)]);
+
+        let texture_desc = wgt::TextureDescriptor {
+            label: (),
+            size: wgt::Extent3d {
+                width: 64,
+                height: 64,
+                depth_or_array_layers: 64,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgt::TextureDimension::D3,
+            format: wgt::TextureFormat::R8Unorm,
+            usage: wgt::TextureUsages::COPY_DST,
+        };
+        let mut regions = Vec::new();
+        // 16 slices would fit into the zero buffer, but only 4 fit into 256 rows.
+        collect_zero_buffer_copies(&texture_desc, 256, 256 << 10, 256, 0..1, 0..1, &mut regions);
+
+        assert_eq!(regions.len(), 16);
+        for region in regions.iter() {
+            assert_eq!(region.size.depth, 4);
+            assert_eq!(region.size.height, 64);
+        }
+    }
+
+    #[test]
+    fn clear_value_matches_format() {
+        let color = |r, g| {
+            RenderPassClearValue::Color(wgt::Color {
+                r,
+                g,
+                b: 0.0,
+                a: 0.0,
+            })
+        };
+        let check = |format, value| validate_clear_value(format, value).is_ok();
+
+        assert!(check(wgt::TextureFormat::Rgba8Unorm, color(0.5, 2.0)));
+        assert!(check(wgt::TextureFormat::R8Uint, color(255.0, 0.5)));
+        assert!(!check(wgt::TextureFormat::R8Uint, color(256.0, 0.0)));
+        assert!(!check(wgt::TextureFormat::Rg8Uint, color(1.0, 0.5)));
+        assert!(!check(wgt::TextureFormat::R16Uint, color(-1.0, 0.0)));
+        assert!(check(
+            wgt::TextureFormat::Rg16Sint,
+            color(-32768.0, 32767.0)
+        ));
+        assert!(!check(wgt::TextureFormat::Rg16Sint, color(0.0, 32768.0)));
+        assert!(check(
+            wgt::TextureFormat::R32Uint,
+            color(u32::MAX as f64, 0.0)
+        ));
+
+        let depth = wgt::TextureFormat::Depth24PlusStencil8;
+        assert!(check(
+            depth,
+            RenderPassClearValue::DepthStencil(1.0, Some(255))
+        ));
+        assert!(!check(depth, RenderPassClearValue::DepthStencil(1.5, None)));
+        assert!(!check(
+            depth,
+            RenderPassClearValue::DepthStencil(0.0, Some(256))
+        ));
+        assert!(matches!(
+            validate_clear_value(depth, color(0.0, 0.0)),
+            Err(ClearError::ColorValueForDepthStencil)
+        ));
+    }
+}
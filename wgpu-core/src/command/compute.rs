@@ -1,27 +1,52 @@
 use crate::{
-    binding_model::{BindError, BindGroup, PushConstantUploadError},
+    binding_model::{BindError, BindGroup, PipelineLayout, PushConstantUploadError},
     command::{
         bind::Binder,
-        end_pipeline_statistics_query,
-        memory_init::{fixup_discarded_surfaces, SurfacesInDiscardState},
-        BasePass, BasePassRef, CommandBuffer, CommandEncoderError, CommandEncoderStatus,
-        MapPassErr, PassErrorScope, QueryUseError, StateChange,
+        debug_marker_label, end_pipeline_statistics_query,
+        memory_init::{
+            fixup_discarded_surfaces, CommandBufferTextureMemoryActions, SurfacesInDiscardState,
+        },
+        BasePass, BasePassRef, CommandBuffer, CommandEncoderError, CommandEncoderStatus, CopySide,
+        MapPassErr, PassErrorScope, QueryUseError, RenderBundle, StateChange, TransferError,
     },
-    device::MissingDownlevelFlags,
+    conv,
+    device::{Device, MissingDownlevelFlags, MissingFeatures},
     error::{ErrorFormatter, PrettyError},
-    hub::{Global, GlobalIdentityHandlerFactory, HalApi, Storage, Token},
+    hub::{Access, Global, GlobalIdentityHandlerFactory, HalApi, Storage, Token},
     id,
-    init_tracker::MemoryInitKind,
+    init_tracker::{BufferInitTrackerAction, MemoryInitKind},
+    pipeline::ComputePipeline,
     resource::{Buffer, Texture},
     track::{StatefulTrackerSubset, TrackerSet, UsageConflict, UseExtendError},
     validation::{check_buffer_usage, MissingBufferUsageError},
-    Label,
+    FastHashMap, FastHashSet, Label,
 };
 
 use hal::CommandEncoder as _;
 use thiserror::Error;
 
-use std::{fmt, mem, str};
+use std::{borrow::Cow, fmt, iter, mem, ops::Range};
+
+/// Serializes `hal::BufferUses`, which has no serde support, as its bits.
+#[cfg(any(feature = "serial-pass", feature = "trace", feature = "replay"))]
+mod buffer_uses_serde {
+    #[cfg(any(feature = "serial-pass", feature = "trace"))]
+    pub fn serialize<S: serde::Serializer>(
+        usage: &hal::BufferUses,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&usage.bits(), serializer)
+    }
+
+    #[cfg(any(feature = "serial-pass", feature = "replay"))]
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<hal::BufferUses, D::Error> {
+        let bits = <u32 as serde::Deserialize>::deserialize(deserializer)?;
+        hal::BufferUses::from_bits(bits)
+            .ok_or_else(|| serde::de::Error::custom("invalid buffer usage bits"))
+    }
+}
 
 #[doc(hidden)]
 #[derive(Clone, Copy, Debug)]
@@ -39,6 +64,12 @@ pub enum ComputeCommand {
         num_dynamic_offsets: u8,
         bind_group_id: id::BindGroupId,
     },
+    /// Set the next `count` bind groups in [`BasePass::bind_groups`] at
+    /// consecutive indices, starting at `first_index`.
+    SetBindGroups {
+        first_index: u8,
+        count: u8,
+    },
     SetPipeline(id::ComputePipelineId),
     SetPushConstant {
         offset: u32,
@@ -46,10 +77,29 @@ pub enum ComputeCommand {
         values_offset: u32,
     },
     Dispatch([u32; 3]),
+    /// Dispatch once for each of the next `count` group counts in
+    /// [`BasePass::dispatch_groups`], flushing the pass state only once.
+    DispatchBatch {
+        count: u32,
+    },
     DispatchIndirect {
         buffer_id: id::BufferId,
         offset: wgt::BufferAddress,
     },
+    DispatchIndirectCount {
+        buffer_id: id::BufferId,
+        offset: wgt::BufferAddress,
+        count_buffer_id: id::BufferId,
+        count_buffer_offset: wgt::BufferAddress,
+        max_count: u32,
+    },
+    /// Dispatch `groups` only if the `u32` at `predicate_offset` in the
+    /// predicate buffer is non-zero when the GPU reaches the dispatch.
+    DispatchIf {
+        predicate_buffer_id: id::BufferId,
+        predicate_offset: wgt::BufferAddress,
+        groups: [u32; 3],
+    },
     PushDebugGroup {
         color: u32,
         len: usize,
@@ -68,12 +118,51 @@ pub enum ComputeCommand {
         query_index: u32,
     },
     EndPipelineStatisticsQuery,
+    /// Transition a single buffer to the `to` usage, from the usage the
+    /// buffer is tracked in.
+    BufferBarrier {
+        buffer_id: id::BufferId,
+        #[cfg_attr(
+            any(feature = "serial-pass", feature = "trace", feature = "replay"),
+            serde(with = "buffer_uses_serde")
+        )]
+        to: hal::BufferUses,
+    },
+    SetPipelineByKey(u64),
+    SetBindGroupByKey {
+        index: u8,
+        num_dynamic_offsets: u8,
+        key: u64,
+    },
+    /// Hint that the given buffer range is going to be read soon.
+    /// Backends are free to ignore it.
+    PrefetchBuffer {
+        buffer_id: id::BufferId,
+        offset: wgt::BufferAddress,
+        size: Option<wgt::BufferSize>,
+    },
+    /// Copy between two buffers without leaving the pass.
+    ///
+    /// Not every backend can record copies inside a compute pass, so the raw
+    /// pass is ended before the copy and begun again after it. The pipeline,
+    /// bind groups and push constants are set again on the new raw pass, so
+    /// the split isn't observable, but it can't happen inside a debug group
+    /// or a pipeline statistics query.
+    CopyBufferToBuffer {
+        src: id::BufferId,
+        src_offset: wgt::BufferAddress,
+        dst: id::BufferId,
+        dst_offset: wgt::BufferAddress,
+        size: wgt::BufferAddress,
+    },
 }
 
 #[cfg_attr(feature = "serial-pass", derive(serde::Deserialize, serde::Serialize))]
 pub struct ComputePass {
     base: BasePass<ComputeCommand>,
     parent_id: id::CommandEncoderId,
+    max_dispatch_count: Option<u32>,
+    batch_barriers: bool,
 }
 
 impl ComputePass {
@@ -81,6 +170,8 @@ impl ComputePass {
         Self {
             base: BasePass::new(&desc.label),
             parent_id,
+            max_dispatch_count: desc.max_dispatch_count,
+            batch_barriers: desc.batch_barriers,
         }
     }
 
@@ -88,9 +179,153 @@ impl ComputePass {
         self.parent_id
     }
 
+    /// Remove all recorded commands, keeping the allocated memory, so that the
+    /// pass can be recorded again, e.g. after [`Global::command_encoder_reset`].
+    pub fn clear(&mut self) {
+        self.base.clear();
+    }
+
+    pub fn label(&self) -> Option<&str> {
+        self.base.label.as_deref()
+    }
+
+    /// Commands recorded into the pass so far.
+    pub fn commands(&self) -> &[ComputeCommand] {
+        &self.base.commands
+    }
+
+    /// Dynamic offsets of all bind groups set in the pass, consumed in order by
+    /// the `num_dynamic_offsets` of each [`ComputeCommand::SetBindGroup`].
+    pub fn dynamic_offsets(&self) -> &[wgt::DynamicOffset] {
+        &self.base.dynamic_offsets
+    }
+
+    /// Push constant values of the pass, as referenced by the `values_offset`
+    /// of [`ComputeCommand::SetPushConstant`] in 4-byte words.
+    pub fn push_constant_data(&self) -> &[u32] {
+        &self.base.push_constant_data
+    }
+
+    /// Total number of dynamic offsets passed to the bind groups set in the pass.
+    pub fn dynamic_offset_count(&self) -> usize {
+        self.base.dynamic_offsets.len()
+    }
+
+    /// Dynamic offsets of every bind group set in the pass, in recording
+    /// order, along with the index the group is set at.
+    pub fn bind_group_dynamic_offsets(
+        &self,
+    ) -> impl Iterator<Item = (u8, &[wgt::DynamicOffset])> + '_ {
+        let mut offset_cursor = 0;
+        // Batches overrunning the recorded bind groups are reported when the pass is run.
+        let commands = expand_bind_group_batches(self.base.as_ref()).unwrap_or(Cow::Borrowed(&[]));
+        (0..commands.len()).filter_map(move |i| match commands[i] {
+            ComputeCommand::SetBindGroup {
+                index,
+                num_dynamic_offsets,
+                ..
+            }
+            | ComputeCommand::SetBindGroupByKey {
+                index,
+                num_dynamic_offsets,
+                ..
+            } => Some((
+                index,
+                next_dynamic_offsets(
+                    &self.base.dynamic_offsets,
+                    &mut offset_cursor,
+                    num_dynamic_offsets,
+                ),
+            )),
+            _ => None,
+        })
+    }
+
+    /// Number of dispatches recorded into the pass, counting each dispatch of
+    /// a batch and each indirect dispatch command once.
+    pub fn dispatch_count(&self) -> usize {
+        self.base
+            .commands
+            .iter()
+            .map(|command| match *command {
+                ComputeCommand::Dispatch(_)
+                | ComputeCommand::DispatchIndirect { .. }
+                | ComputeCommand::DispatchIndirectCount { .. }
+                | ComputeCommand::DispatchIf { .. } => 1,
+                ComputeCommand::DispatchBatch { count } => count as usize,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Returns `true` if running the pass would do no work, so submitting it
+    /// can be skipped.
+    ///
+    /// A pass is empty if it contains no dispatches, buffer copies or buffer
+    /// barriers. Debug markers, timestamp writes and queries alone don't make
+    /// it non-empty, so skipping such a pass also skips them.
+    pub fn is_empty(&self) -> bool {
+        !self.base.commands.iter().any(|command| match *command {
+            ComputeCommand::Dispatch(_)
+            | ComputeCommand::DispatchIndirect { .. }
+            | ComputeCommand::DispatchIndirectCount { .. }
+            | ComputeCommand::DispatchIf { .. }
+            | ComputeCommand::BufferBarrier { .. }
+            | ComputeCommand::CopyBufferToBuffer { .. } => true,
+            ComputeCommand::DispatchBatch { count } => count != 0,
+            ComputeCommand::SetBindGroup { .. }
+            | ComputeCommand::SetBindGroups { .. }
+            | ComputeCommand::SetPipeline(_)
+            | ComputeCommand::SetPushConstant { .. }
+            | ComputeCommand::PushDebugGroup { .. }
+            | ComputeCommand::PopDebugGroup
+            | ComputeCommand::InsertDebugMarker { .. }
+            | ComputeCommand::WriteTimestamp { .. }
+            | ComputeCommand::BeginPipelineStatisticsQuery { .. }
+            | ComputeCommand::EndPipelineStatisticsQuery
+            | ComputeCommand::SetPipelineByKey(_)
+            | ComputeCommand::SetBindGroupByKey { .. }
+            | ComputeCommand::PrefetchBuffer { .. } => false,
+        })
+    }
+
+    /// Records a timestamp write if `device_features` contains
+    /// [`wgt::Features::TIMESTAMP_QUERY`], and does nothing otherwise.
+    ///
+    /// Returns `true` if the timestamp write was recorded.
+    pub fn try_write_timestamp(
+        &mut self,
+        query_set_id: id::QuerySetId,
+        query_index: u32,
+        device_features: wgt::Features,
+    ) -> bool {
+        if !device_features.contains(wgt::Features::TIMESTAMP_QUERY) {
+            return false;
+        }
+        self.base.commands.push(ComputeCommand::WriteTimestamp {
+            query_set_id,
+            query_index,
+        });
+        true
+    }
+
     #[cfg(feature = "trace")]
     pub fn into_command(self) -> crate::device::trace::Command {
-        crate::device::trace::Command::RunComputePass { base: self.base }
+        crate::device::trace::Command::RunComputePass {
+            base: self.base,
+            max_dispatch_count: self.max_dispatch_count,
+            batch_barriers: self.batch_barriers,
+        }
+    }
+
+    /// Drop the binding to the parent encoder, so that the recorded commands
+    /// can be run on any number of encoders.
+    pub fn detach(self) -> DetachedComputePass {
+        DetachedComputePass {
+            base: self.base,
+            max_dispatch_count: self.max_dispatch_count,
+            batch_barriers: self.batch_barriers,
+        }
     }
 }
 
@@ -98,8 +333,30 @@ impl fmt::Debug for ComputePass {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "ComputePass {{ encoder_id: {:?}, data: {:?} commands and {:?} dynamic offsets }}",
+            "ComputePass {{ encoder_id: {:?}, label: {:?}, data: {:?} commands and {:?} dynamic offsets }}",
             self.parent_id,
+            self.label(),
+            self.base.commands.len(),
+            self.base.dynamic_offsets.len()
+        )
+    }
+}
+
+/// A recorded compute pass that is not tied to a command encoder.
+///
+/// See [`Global::command_encoder_run_detached_compute_pass`].
+#[cfg_attr(feature = "serial-pass", derive(serde::Deserialize, serde::Serialize))]
+pub struct DetachedComputePass {
+    base: BasePass<ComputeCommand>,
+    max_dispatch_count: Option<u32>,
+    batch_barriers: bool,
+}
+
+impl fmt::Debug for DetachedComputePass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "DetachedComputePass {{ data: {:?} commands and {:?} dynamic offsets }}",
             self.base.commands.len(),
             self.base.dynamic_offsets.len()
         )
@@ -109,22 +366,269 @@ impl fmt::Debug for ComputePass {
 #[derive(Clone, Debug, Default)]
 pub struct ComputePassDescriptor<'a> {
     pub label: Label<'a>,
+    /// Maximum number of dispatches the pass may contain. Running a pass with
+    /// more dispatches fails instead of recording them. `None` means no limit.
+    pub max_dispatch_count: Option<u32>,
+    /// Skip the barriers of buffers that go from one read-only usage to another,
+    /// if the buffer already had a barrier earlier in the pass.
+    ///
+    /// Barriers involving writes, and all texture barriers, are always inserted,
+    /// so this doesn't affect correctness.
+    pub batch_barriers: bool,
+}
+
+/// Translation table from the resource ids recorded in a [`ComputePass`]
+/// to the ids of the equivalent resources on another (e.g. recovered) device.
+///
+/// Ids that are not present in the table are used as is.
+///
+/// The table also resolves pipelines and bind groups that were recorded by a
+/// user-supplied key instead of an id, so that a pass doesn't need to be
+/// re-recorded when, for example, a pipeline is re-created on shader hot-reload.
+#[derive(Clone, Debug, Default)]
+pub struct ComputePassIdRemap {
+    pub bind_groups: FastHashMap<id::BindGroupId, id::BindGroupId>,
+    pub pipelines: FastHashMap<id::ComputePipelineId, id::ComputePipelineId>,
+    pub buffers: FastHashMap<id::BufferId, id::BufferId>,
+    pub query_sets: FastHashMap<id::QuerySetId, id::QuerySetId>,
+    pub bind_group_keys: FastHashMap<u64, id::BindGroupId>,
+    pub pipeline_keys: FastHashMap<u64, id::ComputePipelineId>,
+}
+
+impl ComputePassIdRemap {
+    fn map<I: Copy + Eq + std::hash::Hash>(table: &FastHashMap<I, I>, id: I) -> I {
+        table.get(&id).copied().unwrap_or(id)
+    }
+
+    fn apply(&self, command: &ComputeCommand) -> ComputeCommand {
+        match *command {
+            ComputeCommand::SetBindGroup {
+                index,
+                num_dynamic_offsets,
+                bind_group_id,
+            } => ComputeCommand::SetBindGroup {
+                index,
+                num_dynamic_offsets,
+                bind_group_id: Self::map(&self.bind_groups, bind_group_id),
+            },
+            ComputeCommand::SetPipeline(pipeline_id) => {
+                ComputeCommand::SetPipeline(Self::map(&self.pipelines, pipeline_id))
+            }
+            ComputeCommand::DispatchIndirect { buffer_id, offset } => {
+                ComputeCommand::DispatchIndirect {
+                    buffer_id: Self::map(&self.buffers, buffer_id),
+                    offset,
+                }
+            }
+            ComputeCommand::DispatchIndirectCount {
+                buffer_id,
+                offset,
+                count_buffer_id,
+                count_buffer_offset,
+                max_count,
+            } => ComputeCommand::DispatchIndirectCount {
+                buffer_id: Self::map(&self.buffers, buffer_id),
+                offset,
+                count_buffer_id: Self::map(&self.buffers, count_buffer_id),
+                count_buffer_offset,
+                max_count,
+            },
+            ComputeCommand::DispatchIf {
+                predicate_buffer_id,
+                predicate_offset,
+                groups,
+            } => ComputeCommand::DispatchIf {
+                predicate_buffer_id: Self::map(&self.buffers, predicate_buffer_id),
+                predicate_offset,
+                groups,
+            },
+            ComputeCommand::WriteTimestamp {
+                query_set_id,
+                query_index,
+            } => ComputeCommand::WriteTimestamp {
+                query_set_id: Self::map(&self.query_sets, query_set_id),
+                query_index,
+            },
+            ComputeCommand::BeginPipelineStatisticsQuery {
+                query_set_id,
+                query_index,
+            } => ComputeCommand::BeginPipelineStatisticsQuery {
+                query_set_id: Self::map(&self.query_sets, query_set_id),
+                query_index,
+            },
+            ComputeCommand::BufferBarrier { buffer_id, to } => ComputeCommand::BufferBarrier {
+                buffer_id: Self::map(&self.buffers, buffer_id),
+                to,
+            },
+            ComputeCommand::PrefetchBuffer {
+                buffer_id,
+                offset,
+                size,
+            } => ComputeCommand::PrefetchBuffer {
+                buffer_id: Self::map(&self.buffers, buffer_id),
+                offset,
+                size,
+            },
+            ComputeCommand::CopyBufferToBuffer {
+                src,
+                src_offset,
+                dst,
+                dst_offset,
+                size,
+            } => ComputeCommand::CopyBufferToBuffer {
+                src: Self::map(&self.buffers, src),
+                src_offset,
+                dst: Self::map(&self.buffers, dst),
+                dst_offset,
+                size,
+            },
+            ComputeCommand::SetPipelineByKey(key) => match self.pipeline_keys.get(&key) {
+                Some(&pipeline_id) => ComputeCommand::SetPipeline(pipeline_id),
+                None => *command,
+            },
+            ComputeCommand::SetBindGroupByKey {
+                index,
+                num_dynamic_offsets,
+                key,
+            } => match self.bind_group_keys.get(&key) {
+                Some(&bind_group_id) => ComputeCommand::SetBindGroup {
+                    index,
+                    num_dynamic_offsets,
+                    bind_group_id,
+                },
+                None => *command,
+            },
+            // The bind groups of the batch are remapped along with `BasePass::bind_groups`.
+            ComputeCommand::SetBindGroups { .. }
+            | ComputeCommand::SetPushConstant { .. }
+            | ComputeCommand::Dispatch(_)
+            | ComputeCommand::DispatchBatch { .. }
+            | ComputeCommand::PushDebugGroup { .. }
+            | ComputeCommand::PopDebugGroup
+            | ComputeCommand::InsertDebugMarker { .. }
+            | ComputeCommand::EndPipelineStatisticsQuery => *command,
+        }
+    }
+}
+
+/// Resources referenced by a [`ComputePass`], as reported by
+/// [`Global::compute_pass_resource_usage`].
+///
+/// Buffer usages are accumulated over the whole pass, including the ones
+/// coming from the bound bind groups.
+#[derive(Clone, Debug, Default)]
+pub struct ResourceUsageSummary {
+    pub buffers: FastHashMap<id::BufferId, hal::BufferUses>,
+    pub textures: FastHashSet<id::TextureId>,
+    pub bind_groups: FastHashSet<id::BindGroupId>,
+    pub pipelines: FastHashSet<id::ComputePipelineId>,
+}
+
+impl ResourceUsageSummary {
+    fn add_buffer(&mut self, buffer_id: id::BufferId, usage: hal::BufferUses) {
+        *self
+            .buffers
+            .entry(buffer_id)
+            .or_insert_with(hal::BufferUses::empty) |= usage;
+    }
 }
 
 #[derive(Clone, Debug, Error, PartialEq)]
 pub enum DispatchError {
     #[error("compute pipeline must be set")]
     MissingPipeline,
-    #[error("current compute pipeline has a layout which is incompatible with a currently set bind group, first differing at entry index {index}")]
+    #[error("current compute pipeline has a layout which is incompatible with a currently set bind group, first differing at entry index {index}: expected layout {expected:?}, provided {provided:?}")]
     IncompatibleBindGroup {
         index: u32,
-        //expected: BindGroupLayoutId,
-        //provided: Option<(BindGroupLayoutId, BindGroupId)>,
+        expected: Option<id::BindGroupLayoutId>,
+        provided: Option<id::BindGroupLayoutId>,
     },
     #[error(
-        "each current dispatch group size dimension ({current:?}) must be less or equal to {limit}"
+        "dispatch group size {current:?} in dimension {dimension} must be less or equal to {limit}"
     )]
-    InvalidGroupSize { current: [u32; 3], limit: u32 },
+    InvalidGroupSize {
+        current: [u32; 3],
+        dimension: char,
+        limit: u32,
+    },
+    #[error("workgroup size {size} of the current compute pipeline in dimension {dimension} must be less or equal to {limit}")]
+    InvalidWorkgroupSize {
+        dimension: char,
+        size: u32,
+        limit: u32,
+    },
+    #[error("compute pass contains more than the maximum of {limit} dispatches")]
+    TooManyDispatches { limit: u32 },
+    #[error("indirect dispatch arguments are {size} bytes long, but 12 bytes are needed")]
+    InvalidIndirectArgsSize { size: usize },
+}
+
+/// Checks each dimension of `groups` against its own `max_compute_workgroups_x/y/z` limit,
+/// and against `max_compute_workgroups_per_dimension`.
+pub fn validate_dispatch_groups(
+    groups: [u32; 3],
+    limits: &wgt::Limits,
+) -> Result<(), DispatchError> {
+    let max_groups = [
+        limits.max_compute_workgroups_x,
+        limits.max_compute_workgroups_y,
+        limits.max_compute_workgroups_z,
+    ]
+    .map(|limit| limit.min(limits.max_compute_workgroups_per_dimension));
+    for ((&size, &limit), dimension) in groups.iter().zip(max_groups.iter()).zip(['x', 'y', 'z']) {
+        if size > limit {
+            return Err(DispatchError::InvalidGroupSize {
+                current: groups,
+                dimension,
+                limit,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Checks that a buffer range used by a bind group still refers to a valid
+/// buffer and lies within its size.
+fn validate_bind_group_buffer_range<A: HalApi>(
+    buffer_guard: &Storage<Buffer<A>, id::BufferId>,
+    action: &BufferInitTrackerAction,
+) -> Result<(), ComputePassErrorInner> {
+    let buffer = buffer_guard
+        .get(action.id)
+        .ok()
+        .filter(|buffer| buffer.raw.is_some())
+        .ok_or(ComputePassErrorInner::InvalidBuffer(action.id))?;
+    if action.range.start > action.range.end || action.range.end > buffer.size {
+        return Err(ComputePassErrorInner::BindGroupBufferRangeOverrun {
+            buffer: action.id,
+            start: action.range.start,
+            end: action.range.end,
+            buffer_size: buffer.size,
+        });
+    }
+    Ok(())
+}
+
+/// Returns the workgroup counts of an indirect dispatch, given the contents
+/// of its argument buffer after it has been read back.
+///
+/// `data` starts with a [`wgt::DispatchIndirectArgs`] in native byte order.
+/// Group counts are validated the same way as for a direct dispatch.
+pub fn indirect_dispatch_groups(
+    data: &[u8],
+    limits: &wgt::Limits,
+) -> Result<[u32; 3], DispatchError> {
+    if data.len() < 12 {
+        return Err(DispatchError::InvalidIndirectArgsSize { size: data.len() });
+    }
+    let mut groups = [0; 3];
+    for (group, chunk) in groups.iter_mut().zip(data.chunks_exact(4)) {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(chunk);
+        *group = u32::from_ne_bytes(bytes);
+    }
+    validate_dispatch_groups(groups, limits)?;
+    Ok(groups)
 }
 
 /// Error encountered when performing a compute pass.
@@ -136,12 +640,26 @@ pub enum ComputePassErrorInner {
     InvalidBindGroup(id::BindGroupId),
     #[error("bind group index {index} is greater than the device's requested `max_bind_group` limit {max}")]
     BindGroupIndexOutOfRange { index: u8, max: u32 },
+    #[error("bind group batch of {count} groups overruns the {available} remaining bind groups of the pass")]
+    BindGroupBatchOverrun { count: u8, available: usize },
+    #[error("dispatch batch of {count} dispatches overruns the {available} remaining group counts of the pass")]
+    DispatchBatchOverrun { count: u32, available: usize },
     #[error("compute pipeline {0:?} is invalid")]
     InvalidPipeline(id::ComputePipelineId),
+    #[error("compute pipeline key {0} was not resolved to a pipeline")]
+    UnresolvedPipelineKey(u64),
+    #[error("bind group key {0} was not resolved to a bind group")]
+    UnresolvedBindGroupKey(u64),
     #[error("QuerySet {0:?} is invalid")]
     InvalidQuerySet(id::QuerySetId),
     #[error("indirect buffer {0:?} is invalid or destroyed")]
     InvalidIndirectBuffer(id::BufferId),
+    #[error("indirect buffer offset {0} must be a multiple of 4")]
+    UnalignedIndirectBufferOffset(u64),
+    #[error("buffer barrier usage {0:?} is not allowed by the usages the buffer was created with")]
+    InvalidBufferBarrierUsage(hal::BufferUses),
+    #[error("indirect count buffer offset {0} must be a multiple of 4")]
+    UnalignedIndirectCountBufferOffset(u64),
     #[error("indirect buffer uses bytes {offset}..{end_offset} which overruns indirect buffer of size {buffer_size}")]
     IndirectBufferOverrun {
         offset: u64,
@@ -150,12 +668,41 @@ pub enum ComputePassErrorInner {
     },
     #[error("buffer {0:?} is invalid or destroyed")]
     InvalidBuffer(id::BufferId),
+    #[error("predicate buffer {0:?} is invalid or destroyed")]
+    InvalidPredicateBuffer(id::BufferId),
+    #[error("predicate offset {0} must be a multiple of 4")]
+    UnalignedPredicateOffset(u64),
+    #[error("predicate uses bytes {offset}..{end_offset} which overruns predicate buffer of size {buffer_size}")]
+    PredicateBufferOverrun {
+        offset: u64,
+        end_offset: u64,
+        buffer_size: u64,
+    },
+    #[error("bind group uses range {start}..{end} of buffer {buffer:?}, which only has {buffer_size} bytes")]
+    BindGroupBufferRangeOverrun {
+        buffer: id::BufferId,
+        start: u64,
+        end: u64,
+        buffer_size: u64,
+    },
+    #[error("prefetch range {offset}..{end_offset} overruns buffer of size {buffer_size}")]
+    PrefetchRangeOverrun {
+        offset: u64,
+        end_offset: u64,
+        buffer_size: u64,
+    },
     #[error(transparent)]
     ResourceUsageConflict(#[from] UsageConflict),
     #[error(transparent)]
     MissingBufferUsage(#[from] MissingBufferUsageError),
     #[error("cannot pop debug group, because number of pushed debug groups is zero")]
     InvalidPopDebugGroup,
+    #[error("debug marker label is out of bounds or not valid UTF-8")]
+    InvalidDebugMarkerLabel,
+    #[error("cannot copy between buffers while a debug group is open")]
+    CopyInsideDebugGroup,
+    #[error("cannot copy between buffers while a pipeline statistics query is active")]
+    CopyInsidePipelineStatisticsQuery,
     #[error(transparent)]
     Dispatch(#[from] DispatchError),
     #[error(transparent)]
@@ -165,6 +712,10 @@ pub enum ComputePassErrorInner {
     #[error(transparent)]
     QueryUse(#[from] QueryUseError),
     #[error(transparent)]
+    Transfer(#[from] TransferError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error(transparent)]
     MissingDownlevelFlags(#[from] MissingDownlevelFlags),
 }
 
@@ -178,9 +729,16 @@ impl PrettyError for ComputePassErrorInner {
             Self::InvalidPipeline(id) => {
                 fmt.compute_pipeline_label(&id);
             }
-            Self::InvalidIndirectBuffer(id) => {
+            Self::InvalidIndirectBuffer(id) | Self::InvalidPredicateBuffer(id) => {
                 fmt.buffer_label(&id);
             }
+            Self::Dispatch(DispatchError::IncompatibleBindGroup {
+                expected, provided, ..
+            }) => {
+                for id in expected.iter().chain(provided.iter()) {
+                    fmt.bind_group_layout_label(id);
+                }
+            }
             _ => {}
         };
     }
@@ -219,26 +777,402 @@ where
 struct State {
     binder: Binder,
     pipeline: StateChange<id::ComputePipelineId>,
+    workgroup_size: Option<[u32; 3]>,
+    /// Whether the pipeline writes to any resource, or was already warned about.
+    pipeline_writes_resources: bool,
+    /// Whether to warn about dispatches of pipelines that write to no resource.
+    warn_pipeline_without_writes: bool,
     trackers: StatefulTrackerSubset,
     debug_scope_depth: u32,
+    dispatch_count: u32,
+    max_dispatch_count: Option<u32>,
+    /// Push constants set while no pipeline was bound, as `(offset, size_bytes, values_offset)`.
+    ///
+    /// They are applied once the next pipeline is set.
+    pending_push_constants: Vec<(u32, u32, u32)>,
+    /// Push constants set since the pipeline layout last changed, in the same form
+    /// as `pending_push_constants`. They are set again when the raw pass is split.
+    applied_push_constants: Vec<(u32, u32, u32)>,
+    /// Whether to warn about a complete setup that isn't dispatched.
+    warn_missing_dispatch: bool,
+    /// Pipeline that is ready to be dispatched, with all the bind groups it needs,
+    /// but wasn't dispatched yet.
+    undispatched_pipeline: Option<id::ComputePipelineId>,
+    /// Whether to skip read-only barriers of buffers that were already flushed in this pass.
+    batch_barriers: bool,
+    /// Buffers that had their barriers flushed in this pass.
+    flushed_buffers: FastHashSet<id::Valid<id::BufferId>>,
+}
+
+/// Returns the dynamic offsets of a `SetBindGroup` command, and moves
+/// `offset_cursor` past them in the dynamic offsets of the pass.
+fn next_dynamic_offsets<'a>(
+    dynamic_offsets: &'a [wgt::DynamicOffset],
+    offset_cursor: &mut usize,
+    num_dynamic_offsets: u8,
+) -> &'a [wgt::DynamicOffset] {
+    let start = *offset_cursor;
+    *offset_cursor += num_dynamic_offsets as usize;
+    &dynamic_offsets[start..*offset_cursor]
+}
+
+/// Expands every [`ComputeCommand::SetBindGroups`] of `base` into one
+/// [`ComputeCommand::SetBindGroup`] per bind group of the batch, so the groups
+/// go through the same validation as individually set ones.
+fn expand_bind_group_batches(
+    base: BasePassRef<ComputeCommand>,
+) -> Result<Cow<[ComputeCommand]>, ComputePassErrorInner> {
+    if !base
+        .commands
+        .iter()
+        .any(|command| matches!(*command, ComputeCommand::SetBindGroups { .. }))
+    {
+        return Ok(Cow::Borrowed(base.commands));
+    }
+
+    let mut commands = Vec::with_capacity(base.commands.len() + base.bind_groups.len());
+    let mut bind_group_offset = 0usize;
+    for command in base.commands {
+        match *command {
+            ComputeCommand::SetBindGroups { first_index, count } => {
+                let batch = bind_group_offset
+                    .checked_add(count as usize)
+                    .and_then(|end| base.bind_groups.get(bind_group_offset..end))
+                    .ok_or(ComputePassErrorInner::BindGroupBatchOverrun {
+                        count,
+                        available: base.bind_groups.len() - bind_group_offset,
+                    })?;
+                bind_group_offset += count as usize;
+                commands.extend(batch.iter().zip(0..).map(
+                    |(&(bind_group_id, num_dynamic_offsets), i)| ComputeCommand::SetBindGroup {
+                        // Out of range indices are caught by the bind group index validation.
+                        index: first_index.saturating_add(i),
+                        num_dynamic_offsets,
+                        bind_group_id,
+                    },
+                ));
+            }
+            _ => commands.push(*command),
+        }
+    }
+    Ok(Cow::Owned(commands))
+}
+
+/// Returns the `count` group counts of a [`ComputeCommand::DispatchBatch`]
+/// starting at `offset` of [`BasePass::dispatch_groups`].
+fn dispatch_batch(
+    dispatch_groups: &[[u32; 3]],
+    offset: usize,
+    count: u32,
+) -> Result<&[[u32; 3]], ComputePassErrorInner> {
+    offset
+        .checked_add(count as usize)
+        .and_then(|end| dispatch_groups.get(offset..end))
+        .ok_or(ComputePassErrorInner::DispatchBatchOverrun {
+            count,
+            available: dispatch_groups.len().saturating_sub(offset),
+        })
+}
+
+/// Returns `true` if a buffer goes from one read-only usage to another, which
+/// can't be a hazard. Transitions between equal usages never get here, as the
+/// tracker only emits them for unordered (i.e. writable storage) usages.
+fn is_read_only_transition(usage: &Range<hal::BufferUses>) -> bool {
+    hal::BufferUses::INCLUSIVE.contains(usage.start)
+        && hal::BufferUses::INCLUSIVE.contains(usage.end)
+}
+
+/// Returns the end of `count` elements of `stride` bytes at `offset` of an
+/// indirect buffer, if they fit into the buffer.
+fn indirect_range_end(
+    offset: wgt::BufferAddress,
+    stride: wgt::BufferAddress,
+    count: u32,
+    buffer_size: wgt::BufferAddress,
+) -> Result<wgt::BufferAddress, ComputePassErrorInner> {
+    match stride
+        .checked_mul(count as wgt::BufferAddress)
+        .and_then(|size| offset.checked_add(size))
+    {
+        Some(end_offset) if end_offset <= buffer_size => Ok(end_offset),
+        end_offset => Err(ComputePassErrorInner::IndirectBufferOverrun {
+            offset,
+            end_offset: end_offset.unwrap_or(wgt::BufferAddress::MAX),
+            buffer_size,
+        }),
+    }
+}
+
+/// Checks that a buffer created with `buffer_usage` can be transitioned to `to`.
+fn check_buffer_barrier_usage(
+    buffer_usage: wgt::BufferUsages,
+    to: hal::BufferUses,
+) -> Result<(), ComputePassErrorInner> {
+    if to.is_empty() || !conv::map_buffer_usage(buffer_usage).contains(to) {
+        return Err(ComputePassErrorInner::InvalidBufferBarrierUsage(to));
+    }
+    Ok(())
+}
+
+/// Returns the end of the dispatch count at `offset` of an indirect count buffer,
+/// if it is aligned and fits into the buffer.
+fn count_buffer_range_end(
+    offset: wgt::BufferAddress,
+    buffer_size: wgt::BufferAddress,
+) -> Result<wgt::BufferAddress, ComputePassErrorInner> {
+    // Required by `ExecuteIndirect` on D3D12, like the argument offset.
+    if offset % 4 != 0 {
+        return Err(ComputePassErrorInner::UnalignedIndirectCountBufferOffset(
+            offset,
+        ));
+    }
+    indirect_range_end(offset, 4, 1, buffer_size)
+}
+
+/// Returns the end of a copy of `size` bytes at `offset`, if it fits into the buffer.
+fn copy_range_end(
+    offset: wgt::BufferAddress,
+    size: wgt::BufferAddress,
+    buffer_size: wgt::BufferAddress,
+    side: CopySide,
+) -> Result<wgt::BufferAddress, TransferError> {
+    match offset.checked_add(size) {
+        Some(end_offset) if end_offset <= buffer_size => Ok(end_offset),
+        end_offset => Err(TransferError::BufferOverrun {
+            start_offset: offset,
+            end_offset: end_offset.unwrap_or(wgt::BufferAddress::MAX),
+            buffer_size,
+            side,
+        }),
+    }
+}
+
+/// Sets all push constant ranges of `pipeline_layout` to zero.
+fn clear_push_constants<A: HalApi>(
+    raw: &mut A::CommandEncoder,
+    pipeline_layout: &PipelineLayout<A>,
+) {
+    let non_overlapping =
+        super::bind::compute_nonoverlapping_ranges(&pipeline_layout.push_constant_ranges);
+    for range in non_overlapping {
+        let offset = range.range.start;
+        let size_bytes = range.range.end - offset;
+        super::push_constant_clear(offset, size_bytes, |clear_offset, clear_data| unsafe {
+            raw.set_push_constants(
+                &pipeline_layout.raw,
+                wgt::ShaderStages::COMPUTE,
+                clear_offset,
+                clear_data,
+            );
+        });
+    }
+}
+
+/// Validates a push constant update against `pipeline_layout` and records it.
+fn set_push_constants<A: HalApi>(
+    raw: &mut A::CommandEncoder,
+    pipeline_layout: &PipelineLayout<A>,
+    push_constant_data: &[u32],
+    offset: u32,
+    size_bytes: u32,
+    values_offset: u32,
+) -> Result<(), PushConstantUploadError> {
+    let end_offset = offset
+        .checked_add(size_bytes)
+        .ok_or(PushConstantUploadError::Overflow { offset, size_bytes })?;
+    pipeline_layout.validate_push_constant_ranges(
+        wgt::ShaderStages::COMPUTE,
+        offset,
+        end_offset,
+    )?;
+
+    // Sliced only after validation, so that malformed commands
+    // from a replayed trace are reported instead of panicking.
+    let values_start = values_offset as usize;
+    let values_end = values_start + (size_bytes / wgt::PUSH_CONSTANT_ALIGNMENT) as usize;
+    let data_slice = push_constant_data.get(values_start..values_end).ok_or(
+        PushConstantUploadError::ValuesOverrun {
+            start: values_start,
+            end: values_end,
+            available: push_constant_data.len(),
+        },
+    )?;
+
+    unsafe {
+        raw.set_push_constants(
+            &pipeline_layout.raw,
+            wgt::ShaderStages::COMPUTE,
+            offset,
+            data_slice,
+        );
+    }
+    Ok(())
 }
 
 impl State {
-    fn is_ready(&self) -> Result<(), DispatchError> {
+    fn count_dispatch(&mut self) -> Result<(), DispatchError> {
+        self.undispatched_pipeline = None;
+        self.dispatch_count += 1;
+        match self.max_dispatch_count {
+            Some(limit) if self.dispatch_count > limit => {
+                Err(DispatchError::TooManyDispatches { limit })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Remembers the pipeline if it is ready to be dispatched after a change
+    /// of the bound state.
+    fn note_setup(&mut self, limits: &wgt::Limits) {
+        if self.warn_missing_dispatch && self.is_ready(limits).is_ok() {
+            self.undispatched_pipeline = self.pipeline.last_state;
+        }
+    }
+
+    /// Warns if a pipeline was set up completely but not dispatched before `event`.
+    fn warn_if_undispatched(&mut self, event: &str) {
+        if let Some(pipeline_id) = self.undispatched_pipeline.take() {
+            log::warn!(
+                "Compute pipeline {:?} and its bind groups were set, but {} without a dispatch",
+                pipeline_id,
+                event
+            );
+        }
+    }
+
+    /// Warns once per pipeline change if the dispatched pipeline can't have any effect,
+    /// which usually means a binding was forgotten.
+    fn warn_if_pipeline_without_writes(&mut self) {
+        if self.warn_pipeline_without_writes && !self.pipeline_writes_resources {
+            log::warn!(
+                "Dispatching compute pipeline {:?}, which doesn't write to any resource",
+                self.pipeline.last_state
+            );
+            self.pipeline_writes_resources = true;
+        }
+    }
+
+    /// Sets the pipeline, bind groups and push constants on a new raw pass,
+    /// after the previous one was ended in the middle of the pass.
+    fn restore_bound_state<A: HalApi>(
+        &self,
+        raw: &mut A::CommandEncoder,
+        pipeline_guard: &Storage<ComputePipeline<A>, id::ComputePipelineId>,
+        pipeline_layout_guard: &Storage<PipelineLayout<A>, id::PipelineLayoutId>,
+        bind_group_guard: &Storage<BindGroup<A>, id::BindGroupId>,
+        push_constant_data: &[u32],
+    ) -> Result<(), PushConstantUploadError> {
+        if let Some(pipeline_id) = self.pipeline.last_state {
+            unsafe {
+                raw.set_compute_pipeline(&pipeline_guard[id::Valid(pipeline_id)].raw);
+            }
+        }
+        let pipeline_layout = match self.binder.pipeline_layout_id {
+            Some(id) => &pipeline_layout_guard[id],
+            None => return Ok(()),
+        };
+        for (index, entry) in self.binder.bound_groups().iter().enumerate() {
+            let raw_bg = &bind_group_guard[entry.group_id.as_ref().unwrap().value].raw;
+            unsafe {
+                raw.set_bind_group(
+                    &pipeline_layout.raw,
+                    index as u32,
+                    raw_bg,
+                    &entry.dynamic_offsets,
+                );
+            }
+        }
+        clear_push_constants(raw, pipeline_layout);
+        for &(offset, size_bytes, values_offset) in self.applied_push_constants.iter() {
+            set_push_constants(
+                raw,
+                pipeline_layout,
+                push_constant_data,
+                offset,
+                size_bytes,
+                values_offset,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn is_ready(&self, limits: &wgt::Limits) -> Result<(), DispatchError> {
         //TODO: vertex buffers
         let bind_mask = self.binder.invalid_mask();
         if bind_mask != 0 {
-            //let (expected, provided) = self.binder.entries[index as usize].info();
+            let index = bind_mask.trailing_zeros();
+            let (expected, provided) = self.binder.group_layouts(index as usize);
             return Err(DispatchError::IncompatibleBindGroup {
-                index: bind_mask.trailing_zeros(),
+                index,
+                expected,
+                provided,
             });
         }
         if self.pipeline.is_unset() {
             return Err(DispatchError::MissingPipeline);
         }
+        if let Some(workgroup_size) = self.workgroup_size {
+            let workgroup_size_limits = [
+                limits.max_compute_workgroup_size_x,
+                limits.max_compute_workgroup_size_y,
+                limits.max_compute_workgroup_size_z,
+            ];
+            for ((dimension, size), limit) in ['x', 'y', 'z']
+                .iter()
+                .zip(workgroup_size)
+                .zip(workgroup_size_limits)
+            {
+                if size > limit {
+                    return Err(DispatchError::InvalidWorkgroupSize {
+                        dimension: *dimension,
+                        size,
+                        limit,
+                    });
+                }
+            }
+        }
         Ok(())
     }
 
+    /// Like [`CommandBuffer::insert_barriers`], but skips the transitions between
+    /// read-only usages of buffers that were already flushed in this pass.
+    fn insert_changed_barriers<A: HalApi>(
+        &mut self,
+        raw_encoder: &mut A::CommandEncoder,
+        base_trackers: &mut TrackerSet,
+        buffer_guard: &Storage<Buffer<A>, id::BufferId>,
+        texture_guard: &Storage<Texture<A>, id::TextureId>,
+    ) {
+        let flushed_buffers = &self.flushed_buffers;
+        let buffer_barriers = base_trackers
+            .buffers
+            .merge_replace(&self.trackers.buffers)
+            .filter(|pending| {
+                !(is_read_only_transition(&pending.usage) && flushed_buffers.contains(&pending.id))
+            })
+            .map(|pending| {
+                let buf = &buffer_guard[pending.id];
+                pending.into_hal(buf)
+            });
+        unsafe {
+            raw_encoder.transition_buffers(buffer_barriers);
+        }
+
+        // Texture layouts may differ between read-only usages, so these are all kept.
+        let texture_barriers = base_trackers
+            .textures
+            .merge_replace(&self.trackers.textures)
+            .map(|pending| {
+                let tex = &texture_guard[pending.id];
+                pending.into_hal(tex)
+            });
+        unsafe {
+            raw_encoder.transition_textures(texture_barriers);
+        }
+
+        self.flushed_buffers.extend(self.trackers.buffers.used());
+    }
+
     fn flush_states<A: HalApi>(
         &mut self,
         raw_encoder: &mut A::CommandEncoder,
@@ -255,20 +1189,34 @@ impl State {
 
         log::trace!("Encoding dispatch barriers");
 
-        CommandBuffer::insert_barriers(
-            raw_encoder,
-            base_trackers,
-            &self.trackers.buffers,
-            &self.trackers.textures,
-            buffer_guard,
-            texture_guard,
-        );
+        if self.batch_barriers {
+            self.insert_changed_barriers(raw_encoder, base_trackers, buffer_guard, texture_guard);
+        } else {
+            CommandBuffer::insert_barriers(
+                raw_encoder,
+                base_trackers,
+                &self.trackers.buffers,
+                &self.trackers.textures,
+                buffer_guard,
+                texture_guard,
+            );
+        }
 
         self.trackers.clear();
         Ok(())
     }
 }
 
+/// The parts of a command buffer a compute pass is recorded into.
+pub(crate) struct ComputePassTarget<'a, A: HalApi> {
+    pub raw: &'a mut A::CommandEncoder,
+    pub trackers: &'a mut TrackerSet,
+    pub buffer_memory_init_actions: &'a mut Vec<BufferInitTrackerAction>,
+    pub texture_memory_actions: &'a mut CommandBufferTextureMemoryActions,
+    pub limits: &'a wgt::Limits,
+    pub dispatch_count: &'a mut usize,
+}
+
 // Common routines between render/compute
 
 impl<G: GlobalIdentityHandlerFactory> Global<G> {
@@ -277,41 +1225,339 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         encoder_id: id::CommandEncoderId,
         pass: &ComputePass,
     ) -> Result<(), ComputePassError> {
-        self.command_encoder_run_compute_pass_impl::<A>(encoder_id, pass.base.as_ref())
+        self.command_encoder_run_compute_pass_impl::<A>(
+            encoder_id,
+            pass.base.as_ref(),
+            pass.max_dispatch_count,
+            pass.batch_barriers,
+        )
     }
 
-    #[doc(hidden)]
-    pub fn command_encoder_run_compute_pass_impl<A: HalApi>(
+    /// Run a previously recorded compute pass, translating its resource ids
+    /// through `remap` first. This allows re-running the same pass after the
+    /// resources were re-created, e.g. on a recovered device.
+    pub fn command_encoder_run_compute_pass_remapped<A: HalApi>(
         &self,
         encoder_id: id::CommandEncoderId,
-        base: BasePassRef<ComputeCommand>,
+        pass: &ComputePass,
+        remap: &ComputePassIdRemap,
+    ) -> Result<(), ComputePassError> {
+        let commands = pass
+            .base
+            .commands
+            .iter()
+            .map(|command| remap.apply(command))
+            .collect::<Vec<_>>();
+        let bind_groups = pass
+            .base
+            .bind_groups
+            .iter()
+            .map(|&(bind_group_id, num_dynamic_offsets)| {
+                (
+                    ComputePassIdRemap::map(&remap.bind_groups, bind_group_id),
+                    num_dynamic_offsets,
+                )
+            })
+            .collect::<Vec<_>>();
+        let base = BasePassRef {
+            commands: &commands,
+            bind_groups: &bind_groups,
+            ..pass.base.as_ref()
+        };
+        self.command_encoder_run_compute_pass_impl::<A>(
+            encoder_id,
+            base,
+            pass.max_dispatch_count,
+            pass.batch_barriers,
+        )
+    }
+
+    /// Run a detached compute pass on `encoder_id`.
+    ///
+    /// All resources referenced by the pass are checked to still be alive
+    /// before anything is recorded, so a pass referencing a dropped or
+    /// destroyed resource fails without leaving a partial pass behind.
+    pub fn command_encoder_run_detached_compute_pass<A: HalApi>(
+        &self,
+        encoder_id: id::CommandEncoderId,
+        pass: &DetachedComputePass,
+    ) -> Result<(), ComputePassError> {
+        self.validate_detached_compute_pass::<A>(encoder_id, pass)?;
+        self.command_encoder_run_compute_pass_impl::<A>(
+            encoder_id,
+            pass.base.as_ref(),
+            pass.max_dispatch_count,
+            pass.batch_barriers,
+        )
+    }
+
+    fn validate_detached_compute_pass<A: HalApi>(
+        &self,
+        encoder_id: id::CommandEncoderId,
+        pass: &DetachedComputePass,
     ) -> Result<(), ComputePassError> {
-        profiling::scope!("run_compute_pass", "CommandEncoder");
         let scope = PassErrorScope::Pass(encoder_id);
 
         let hub = A::hub(self);
         let mut token = Token::root();
-
-        let (device_guard, mut token) = hub.devices.read(&mut token);
-
-        let (mut cmd_buf_guard, mut token) = hub.command_buffers.write(&mut token);
-        let cmd_buf =
-            CommandBuffer::get_encoder_mut(&mut *cmd_buf_guard, encoder_id).map_pass_err(scope)?;
-        // will be reset to true if recording is done without errors
-        cmd_buf.status = CommandEncoderStatus::Error;
-        let raw = cmd_buf.encoder.open();
-
-        let device = &device_guard[cmd_buf.device_id.value];
-
-        #[cfg(feature = "trace")]
-        if let Some(ref mut list) = cmd_buf.commands {
-            list.push(crate::device::trace::Command::RunComputePass {
-                base: BasePass::from_ref(base),
-            });
-        }
-
-        let (_, mut token) = hub.render_bundles.read(&mut token);
-        let (pipeline_layout_guard, mut token) = hub.pipeline_layouts.read(&mut token);
+        let (bind_group_guard, mut token) = hub.bind_groups.read(&mut token);
+        let (pipeline_guard, mut token) = hub.compute_pipelines.read(&mut token);
+        let (query_set_guard, mut token) = hub.query_sets.read(&mut token);
+        let (buffer_guard, _) = hub.buffers.read(&mut token);
+
+        let check_buffer = |buffer_id: id::BufferId| match buffer_guard.get(buffer_id) {
+            Ok(buffer) if buffer.raw.is_some() => Ok(()),
+            _ => Err(ComputePassErrorInner::InvalidBuffer(buffer_id)),
+        };
+
+        let commands = expand_bind_group_batches(pass.base.as_ref()).map_pass_err(scope)?;
+        for command in commands.iter() {
+            match *command {
+                ComputeCommand::SetBindGroup { bind_group_id, .. } => {
+                    bind_group_guard
+                        .get(bind_group_id)
+                        .map_err(|_| ComputePassErrorInner::InvalidBindGroup(bind_group_id))
+                        .map_pass_err(PassErrorScope::SetBindGroup(bind_group_id))?;
+                }
+                ComputeCommand::SetPipeline(pipeline_id) => {
+                    pipeline_guard
+                        .get(pipeline_id)
+                        .map_err(|_| ComputePassErrorInner::InvalidPipeline(pipeline_id))
+                        .map_pass_err(PassErrorScope::SetPipelineCompute(pipeline_id))?;
+                }
+                ComputeCommand::DispatchIndirect { buffer_id, .. } => {
+                    check_buffer(buffer_id).map_pass_err(scope)?;
+                }
+                ComputeCommand::DispatchIndirectCount {
+                    buffer_id,
+                    count_buffer_id,
+                    ..
+                } => {
+                    check_buffer(buffer_id).map_pass_err(scope)?;
+                    check_buffer(count_buffer_id).map_pass_err(scope)?;
+                }
+                ComputeCommand::DispatchIf {
+                    predicate_buffer_id,
+                    ..
+                } => {
+                    check_buffer(predicate_buffer_id).map_pass_err(scope)?;
+                }
+                ComputeCommand::BufferBarrier { buffer_id, .. } => {
+                    check_buffer(buffer_id)
+                        .map_pass_err(PassErrorScope::BufferBarrier(buffer_id))?;
+                }
+                ComputeCommand::PrefetchBuffer { buffer_id, .. } => {
+                    check_buffer(buffer_id)
+                        .map_pass_err(PassErrorScope::PrefetchBuffer(buffer_id))?;
+                }
+                ComputeCommand::CopyBufferToBuffer { src, dst, .. } => {
+                    check_buffer(src).map_pass_err(PassErrorScope::CopyBufferToBuffer)?;
+                    check_buffer(dst).map_pass_err(PassErrorScope::CopyBufferToBuffer)?;
+                }
+                ComputeCommand::WriteTimestamp { query_set_id, .. }
+                | ComputeCommand::BeginPipelineStatisticsQuery { query_set_id, .. } => {
+                    query_set_guard
+                        .get(query_set_id)
+                        .map_err(|_| ComputePassErrorInner::InvalidQuerySet(query_set_id))
+                        .map_pass_err(scope)?;
+                }
+                ComputeCommand::SetPipelineByKey(key) => {
+                    return Err(ComputePassErrorInner::UnresolvedPipelineKey(key))
+                        .map_pass_err(scope);
+                }
+                ComputeCommand::SetBindGroupByKey { key, .. } => {
+                    return Err(ComputePassErrorInner::UnresolvedBindGroupKey(key))
+                        .map_pass_err(scope);
+                }
+                // Batches are expanded into `SetBindGroup` commands.
+                ComputeCommand::SetBindGroups { .. }
+                | ComputeCommand::SetPushConstant { .. }
+                | ComputeCommand::Dispatch(_)
+                | ComputeCommand::DispatchBatch { .. }
+                | ComputeCommand::PushDebugGroup { .. }
+                | ComputeCommand::PopDebugGroup
+                | ComputeCommand::InsertDebugMarker { .. }
+                | ComputeCommand::EndPipelineStatisticsQuery => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Collect the resources a recorded compute pass references, without
+    /// executing it. This is meant for computing dependencies between passes
+    /// ahead of submission.
+    pub fn compute_pass_resource_usage<A: HalApi>(
+        &self,
+        pass: &ComputePass,
+    ) -> Result<ResourceUsageSummary, ComputePassError> {
+        profiling::scope!("compute_pass_resource_usage", "ComputePass");
+        let scope = PassErrorScope::Pass(pass.parent_id());
+
+        let hub = A::hub(self);
+        let mut token = Token::root();
+        let (bind_group_guard, _) = hub.bind_groups.read(&mut token);
+
+        let mut summary = ResourceUsageSummary::default();
+        let commands = expand_bind_group_batches(pass.base.as_ref()).map_pass_err(scope)?;
+        for command in commands.iter() {
+            match *command {
+                ComputeCommand::SetBindGroup { bind_group_id, .. } => {
+                    let scope = PassErrorScope::SetBindGroup(bind_group_id);
+                    let bind_group = bind_group_guard
+                        .get(bind_group_id)
+                        .map_err(|_| ComputePassErrorInner::InvalidBindGroup(bind_group_id))
+                        .map_pass_err(scope)?;
+                    summary.bind_groups.insert(bind_group_id);
+                    for id in bind_group.used.buffers.used() {
+                        let usage = bind_group
+                            .used
+                            .buffers
+                            .query(id, ())
+                            .unwrap_or_else(hal::BufferUses::empty);
+                        summary.add_buffer(id.0, usage);
+                    }
+                    summary
+                        .textures
+                        .extend(bind_group.used.textures.used().map(|id| id.0));
+                }
+                ComputeCommand::SetPipeline(pipeline_id) => {
+                    summary.pipelines.insert(pipeline_id);
+                }
+                ComputeCommand::DispatchIndirect { buffer_id, .. } => {
+                    summary.add_buffer(buffer_id, hal::BufferUses::INDIRECT);
+                }
+                ComputeCommand::DispatchIndirectCount {
+                    buffer_id,
+                    count_buffer_id,
+                    ..
+                } => {
+                    summary.add_buffer(buffer_id, hal::BufferUses::INDIRECT);
+                    summary.add_buffer(count_buffer_id, hal::BufferUses::INDIRECT);
+                }
+                ComputeCommand::DispatchIf {
+                    predicate_buffer_id,
+                    ..
+                } => {
+                    summary.add_buffer(predicate_buffer_id, hal::BufferUses::PREDICATE);
+                }
+                ComputeCommand::BufferBarrier { buffer_id, to } => {
+                    summary.add_buffer(buffer_id, to);
+                }
+                ComputeCommand::CopyBufferToBuffer { src, dst, .. } => {
+                    summary.add_buffer(src, hal::BufferUses::COPY_SRC);
+                    summary.add_buffer(dst, hal::BufferUses::COPY_DST);
+                }
+                ComputeCommand::SetPipelineByKey(key) => {
+                    return Err(ComputePassErrorInner::UnresolvedPipelineKey(key))
+                        .map_pass_err(scope);
+                }
+                ComputeCommand::SetBindGroupByKey { key, .. } => {
+                    return Err(ComputePassErrorInner::UnresolvedBindGroupKey(key))
+                        .map_pass_err(scope);
+                }
+                // Batches are expanded into `SetBindGroup` commands.
+                ComputeCommand::SetBindGroups { .. }
+                | ComputeCommand::SetPushConstant { .. }
+                | ComputeCommand::Dispatch(_)
+                | ComputeCommand::DispatchBatch { .. }
+                | ComputeCommand::PushDebugGroup { .. }
+                | ComputeCommand::PopDebugGroup
+                | ComputeCommand::InsertDebugMarker { .. }
+                | ComputeCommand::WriteTimestamp { .. }
+                | ComputeCommand::BeginPipelineStatisticsQuery { .. }
+                | ComputeCommand::EndPipelineStatisticsQuery
+                | ComputeCommand::PrefetchBuffer { .. } => {}
+            }
+        }
+        Ok(summary)
+    }
+
+    #[doc(hidden)]
+    pub fn command_encoder_run_compute_pass_impl<A: HalApi>(
+        &self,
+        encoder_id: id::CommandEncoderId,
+        base: BasePassRef<ComputeCommand>,
+        max_dispatch_count: Option<u32>,
+        batch_barriers: bool,
+    ) -> Result<(), ComputePassError> {
+        profiling::scope!("run_compute_pass", "CommandEncoder");
+        let scope = PassErrorScope::Pass(encoder_id);
+
+        let hub = A::hub(self);
+        let mut token = Token::root();
+
+        let (device_guard, mut token) = hub.devices.read(&mut token);
+
+        let (mut cmd_buf_guard, mut token) = hub.command_buffers.write(&mut token);
+        let cmd_buf =
+            CommandBuffer::get_encoder_mut(&mut *cmd_buf_guard, encoder_id).map_pass_err(scope)?;
+        // will be reset to true if recording is done without errors
+        cmd_buf.status = CommandEncoderStatus::Error;
+
+        let device = &device_guard[cmd_buf.device_id.value];
+
+        #[cfg(feature = "trace")]
+        if let Some(ref mut list) = cmd_buf.commands {
+            list.push(crate::device::trace::Command::RunComputePass {
+                base: BasePass::from_ref(base),
+                max_dispatch_count,
+                batch_barriers,
+            });
+        }
+
+        let target = ComputePassTarget {
+            raw: cmd_buf.encoder.open(),
+            trackers: &mut cmd_buf.trackers,
+            buffer_memory_init_actions: &mut cmd_buf.buffer_memory_init_actions,
+            texture_memory_actions: &mut cmd_buf.texture_memory_actions,
+            limits: &cmd_buf.limits,
+            dispatch_count: &mut cmd_buf.dispatch_count,
+        };
+        self.run_compute_pass_on_target::<A, _>(
+            device,
+            scope,
+            target,
+            base,
+            max_dispatch_count,
+            batch_barriers,
+            &mut token,
+        )?;
+        cmd_buf.status = CommandEncoderStatus::Recording;
+
+        Ok(())
+    }
+
+    /// Record a compute pass into an explicitly provided encoder and trackers,
+    /// instead of the ones of a command buffer looked up by id.
+    ///
+    /// This doesn't lock the command buffer storage, so passes targeting
+    /// different encoders can be recorded from several threads at once.
+    /// Errors that don't belong to a single command are reported with `scope`.
+    //TODO: expose this once there are secondary command encoders whose
+    // trackers and command buffers are merged into a primary one at submission.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn run_compute_pass_on_target<A: HalApi, T: Access<RenderBundle>>(
+        &self,
+        device: &Device<A>,
+        scope: PassErrorScope,
+        target: ComputePassTarget<A>,
+        base: BasePassRef<ComputeCommand>,
+        max_dispatch_count: Option<u32>,
+        batch_barriers: bool,
+        token: &mut Token<T>,
+    ) -> Result<(), ComputePassError> {
+        let hub = A::hub(self);
+        let ComputePassTarget {
+            raw,
+            trackers,
+            buffer_memory_init_actions,
+            texture_memory_actions,
+            limits,
+            dispatch_count,
+        } = target;
+
+        let (_, mut token) = hub.render_bundles.read(token);
+        let (pipeline_layout_guard, mut token) = hub.pipeline_layouts.read(&mut token);
         let (bind_group_guard, mut token) = hub.bind_groups.read(&mut token);
         let (pipeline_guard, mut token) = hub.compute_pipelines.read(&mut token);
         let (query_set_guard, mut token) = hub.query_sets.read(&mut token);
@@ -321,14 +1567,28 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let mut state = State {
             binder: Binder::new(),
             pipeline: StateChange::new(),
+            workgroup_size: None,
+            pipeline_writes_resources: true,
+            warn_pipeline_without_writes: device.warn_pipeline_without_writes,
             trackers: StatefulTrackerSubset::new(A::VARIANT),
             debug_scope_depth: 0,
+            dispatch_count: 0,
+            max_dispatch_count,
+            pending_push_constants: Vec::new(),
+            applied_push_constants: Vec::new(),
+            warn_missing_dispatch: device.warn_missing_dispatch,
+            undispatched_pipeline: None,
+            batch_barriers,
+            flushed_buffers: FastHashSet::default(),
         };
         let mut temp_offsets = Vec::new();
         let mut dynamic_offset_count = 0;
         let mut string_offset = 0;
+        let mut dispatch_group_offset = 0;
         let mut active_query = None;
 
+        let commands = expand_bind_group_batches(base).map_pass_err(scope)?;
+
         let hal_desc = hal::ComputePassDescriptor { label: base.label };
         unsafe {
             raw.begin_compute_pass(&hal_desc);
@@ -337,388 +1597,855 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         // Immediate texture inits required because of prior discards. Need to be inserted before texture reads.
         let mut pending_discard_init_fixups = SurfacesInDiscardState::new();
 
-        for command in base.commands {
-            match *command {
-                ComputeCommand::SetBindGroup {
-                    index,
-                    num_dynamic_offsets,
-                    bind_group_id,
-                } => {
-                    let scope = PassErrorScope::SetBindGroup(bind_group_id);
-
-                    let max_bind_groups = cmd_buf.limits.max_bind_groups;
-                    if (index as u32) >= max_bind_groups {
-                        return Err(ComputePassErrorInner::BindGroupIndexOutOfRange {
-                            index,
-                            max: max_bind_groups,
-                        })
-                        .map_pass_err(scope);
-                    }
-
-                    temp_offsets.clear();
-                    temp_offsets.extend_from_slice(
-                        &base.dynamic_offsets[dynamic_offset_count
-                            ..dynamic_offset_count + (num_dynamic_offsets as usize)],
-                    );
-                    dynamic_offset_count += num_dynamic_offsets as usize;
-
-                    let bind_group = cmd_buf
-                        .trackers
-                        .bind_groups
-                        .use_extend(&*bind_group_guard, bind_group_id, (), ())
-                        .map_err(|_| ComputePassErrorInner::InvalidBindGroup(bind_group_id))
-                        .map_pass_err(scope)?;
-                    bind_group
-                        .validate_dynamic_bindings(&temp_offsets, &cmd_buf.limits)
-                        .map_pass_err(scope)?;
-
-                    cmd_buf.buffer_memory_init_actions.extend(
-                        bind_group.used_buffer_ranges.iter().filter_map(
-                            |action| match buffer_guard.get(action.id) {
-                                Ok(buffer) => buffer.initialization_status.check_action(action),
-                                Err(_) => None,
-                            },
-                        ),
-                    );
-
-                    for action in bind_group.used_texture_ranges.iter() {
-                        pending_discard_init_fixups.extend(
-                            cmd_buf
-                                .texture_memory_actions
-                                .register_init_action(action, &texture_guard),
+        // Commands are recorded in a closure, so that an early return on error
+        // still reaches the `end_compute_pass` below.
+        let result = (|| -> Result<(), ComputePassError> {
+            for command in commands.iter() {
+                match *command {
+                    ComputeCommand::SetBindGroup {
+                        index,
+                        num_dynamic_offsets,
+                        bind_group_id,
+                    } => {
+                        let scope = PassErrorScope::SetBindGroup(bind_group_id);
+
+                        // Consumed before any validation, so that the offsets of
+                        // the following commands are read correctly in any case.
+                        let offsets = next_dynamic_offsets(
+                            base.dynamic_offsets,
+                            &mut dynamic_offset_count,
+                            num_dynamic_offsets,
                         );
-                    }
 
-                    let pipeline_layout_id = state.binder.pipeline_layout_id;
-                    let entries = state.binder.assign_group(
-                        index as usize,
-                        id::Valid(bind_group_id),
-                        bind_group,
-                        &temp_offsets,
-                    );
-                    if !entries.is_empty() {
-                        let pipeline_layout =
-                            &pipeline_layout_guard[pipeline_layout_id.unwrap()].raw;
-                        for (i, e) in entries.iter().enumerate() {
-                            let raw_bg = &bind_group_guard[e.group_id.as_ref().unwrap().value].raw;
-                            unsafe {
-                                raw.set_bind_group(
-                                    pipeline_layout,
-                                    index as u32 + i as u32,
-                                    raw_bg,
-                                    &e.dynamic_offsets,
-                                );
-                            }
+                        let max_bind_groups = limits.max_bind_groups;
+                        if (index as u32) >= max_bind_groups {
+                            return Err(ComputePassErrorInner::BindGroupIndexOutOfRange {
+                                index,
+                                max: max_bind_groups,
+                            })
+                            .map_pass_err(scope);
                         }
-                    }
-                }
-                ComputeCommand::SetPipeline(pipeline_id) => {
-                    let scope = PassErrorScope::SetPipelineCompute(pipeline_id);
 
-                    if state.pipeline.set_and_check_redundant(pipeline_id) {
-                        continue;
-                    }
+                        temp_offsets.clear();
+                        temp_offsets.extend_from_slice(offsets);
+
+                        let bind_group = trackers
+                            .bind_groups
+                            .use_extend(&*bind_group_guard, bind_group_id, (), ())
+                            .map_err(|_| ComputePassErrorInner::InvalidBindGroup(bind_group_id))
+                            .map_pass_err(scope)?;
+                        bind_group
+                            .validate_dynamic_bindings(&temp_offsets, limits)
+                            .map_pass_err(scope)?;
+
+                        if cfg!(debug_assertions) {
+                            for action in bind_group.used_buffer_ranges.iter() {
+                                validate_bind_group_buffer_range(&*buffer_guard, action)
+                                    .map_pass_err(scope)?;
+                            }
+                        }
 
-                    let pipeline = cmd_buf
-                        .trackers
-                        .compute_pipes
-                        .use_extend(&*pipeline_guard, pipeline_id, (), ())
-                        .map_err(|_| ComputePassErrorInner::InvalidPipeline(pipeline_id))
-                        .map_pass_err(scope)?;
+                        buffer_memory_init_actions.extend(
+                            bind_group.used_buffer_ranges.iter().filter_map(|action| {
+                                match buffer_guard.get(action.id) {
+                                    Ok(buffer) => buffer.initialization_status.check_action(action),
+                                    Err(_) => None,
+                                }
+                            }),
+                        );
 
-                    unsafe {
-                        raw.set_compute_pipeline(&pipeline.raw);
-                    }
+                        for action in bind_group.used_texture_ranges.iter() {
+                            pending_discard_init_fixups.extend(
+                                texture_memory_actions.register_init_action(action, &texture_guard),
+                            );
+                        }
 
-                    // Rebind resources
-                    if state.binder.pipeline_layout_id != Some(pipeline.layout_id.value) {
-                        let pipeline_layout = &pipeline_layout_guard[pipeline.layout_id.value];
+                        // The bookkeeping above is still needed, as it is per use of the bind group.
+                        if state.binder.is_group_assigned(
+                            index as usize,
+                            id::Valid(bind_group_id),
+                            &temp_offsets,
+                        ) {
+                            log::trace!(
+                                "Skipping redundant set of bind group {:?} at index {}",
+                                bind_group_id,
+                                index
+                            );
+                            continue;
+                        }
 
-                        let (start_index, entries) = state.binder.change_pipeline_layout(
-                            &*pipeline_layout_guard,
-                            pipeline.layout_id.value,
+                        let pipeline_layout_id = state.binder.pipeline_layout_id;
+                        let entries = state.binder.assign_group(
+                            index as usize,
+                            id::Valid(bind_group_id),
+                            bind_group,
+                            &temp_offsets,
                         );
                         if !entries.is_empty() {
+                            let pipeline_layout =
+                                &pipeline_layout_guard[pipeline_layout_id.unwrap()].raw;
                             for (i, e) in entries.iter().enumerate() {
                                 let raw_bg =
                                     &bind_group_guard[e.group_id.as_ref().unwrap().value].raw;
                                 unsafe {
                                     raw.set_bind_group(
-                                        &pipeline_layout.raw,
-                                        start_index as u32 + i as u32,
+                                        pipeline_layout,
+                                        index as u32 + i as u32,
                                         raw_bg,
                                         &e.dynamic_offsets,
                                     );
                                 }
                             }
                         }
+                        state.note_setup(limits);
+                    }
+                    ComputeCommand::SetPipeline(pipeline_id) => {
+                        let scope = PassErrorScope::SetPipelineCompute(pipeline_id);
 
-                        // Clear push constant ranges
-                        let non_overlapping = super::bind::compute_nonoverlapping_ranges(
-                            &pipeline_layout.push_constant_ranges,
-                        );
-                        for range in non_overlapping {
-                            let offset = range.range.start;
-                            let size_bytes = range.range.end - offset;
-                            super::push_constant_clear(
+                        if state.pipeline.set_and_check_redundant(pipeline_id) {
+                            continue;
+                        }
+                        state.warn_if_undispatched("the pipeline was changed");
+
+                        let pipeline = trackers
+                            .compute_pipes
+                            .use_extend(&*pipeline_guard, pipeline_id, (), ())
+                            .map_err(|_| ComputePassErrorInner::InvalidPipeline(pipeline_id))
+                            .map_pass_err(scope)?;
+                        state.workgroup_size = pipeline.workgroup_size;
+                        state.pipeline_writes_resources = pipeline.writes_resources;
+
+                        unsafe {
+                            raw.set_compute_pipeline(&pipeline.raw);
+                        }
+
+                        // Rebind resources
+                        if state.binder.pipeline_layout_id != Some(pipeline.layout_id.value) {
+                            let pipeline_layout = &pipeline_layout_guard[pipeline.layout_id.value];
+
+                            let (start_index, entries) = state.binder.change_pipeline_layout(
+                                &*pipeline_layout_guard,
+                                pipeline.layout_id.value,
+                            );
+                            if !entries.is_empty() {
+                                for (i, e) in entries.iter().enumerate() {
+                                    let raw_bg =
+                                        &bind_group_guard[e.group_id.as_ref().unwrap().value].raw;
+                                    unsafe {
+                                        raw.set_bind_group(
+                                            &pipeline_layout.raw,
+                                            start_index as u32 + i as u32,
+                                            raw_bg,
+                                            &e.dynamic_offsets,
+                                        );
+                                    }
+                                }
+                            }
+
+                            // Clear push constant ranges
+                            clear_push_constants(raw, pipeline_layout);
+                            state.applied_push_constants.clear();
+                        }
+
+                        let pipeline_layout = &pipeline_layout_guard[pipeline.layout_id.value];
+                        for (offset, size_bytes, values_offset) in
+                            state.pending_push_constants.drain(..)
+                        {
+                            set_push_constants(
+                                raw,
+                                pipeline_layout,
+                                base.push_constant_data,
                                 offset,
                                 size_bytes,
-                                |clear_offset, clear_data| unsafe {
-                                    raw.set_push_constants(
-                                        &pipeline_layout.raw,
-                                        wgt::ShaderStages::COMPUTE,
-                                        clear_offset,
-                                        clear_data,
-                                    );
-                                },
-                            );
+                                values_offset,
+                            )
+                            .map_pass_err(PassErrorScope::SetPushConstant)?;
+                            state
+                                .applied_push_constants
+                                .push((offset, size_bytes, values_offset));
                         }
+                        state.note_setup(limits);
                     }
-                }
-                ComputeCommand::SetPushConstant {
-                    offset,
-                    size_bytes,
-                    values_offset,
-                } => {
-                    let scope = PassErrorScope::SetPushConstant;
-
-                    let end_offset_bytes = offset + size_bytes;
-                    let values_end_offset =
-                        (values_offset + size_bytes / wgt::PUSH_CONSTANT_ALIGNMENT) as usize;
-                    let data_slice =
-                        &base.push_constant_data[(values_offset as usize)..values_end_offset];
-
-                    let pipeline_layout_id = state
-                        .binder
-                        .pipeline_layout_id
-                        //TODO: don't error here, lazily update the push constants
-                        .ok_or(ComputePassErrorInner::Dispatch(
-                            DispatchError::MissingPipeline,
-                        ))
-                        .map_pass_err(scope)?;
-                    let pipeline_layout = &pipeline_layout_guard[pipeline_layout_id];
-
-                    pipeline_layout
-                        .validate_push_constant_ranges(
-                            wgt::ShaderStages::COMPUTE,
+                    ComputeCommand::SetPushConstant {
+                        offset,
+                        size_bytes,
+                        values_offset,
+                    } => {
+                        let scope = PassErrorScope::SetPushConstant;
+
+                        let pipeline_layout_id = match state.binder.pipeline_layout_id {
+                            Some(id) => id,
+                            None => {
+                                // Validated against the layout of the next pipeline.
+                                state.pending_push_constants.push((
+                                    offset,
+                                    size_bytes,
+                                    values_offset,
+                                ));
+                                continue;
+                            }
+                        };
+                        set_push_constants(
+                            raw,
+                            &pipeline_layout_guard[pipeline_layout_id],
+                            base.push_constant_data,
                             offset,
-                            end_offset_bytes,
+                            size_bytes,
+                            values_offset,
                         )
                         .map_pass_err(scope)?;
-
-                    unsafe {
-                        raw.set_push_constants(
-                            &pipeline_layout.raw,
-                            wgt::ShaderStages::COMPUTE,
-                            offset,
-                            data_slice,
-                        );
+                        state
+                            .applied_push_constants
+                            .push((offset, size_bytes, values_offset));
                     }
-                }
-                ComputeCommand::Dispatch(groups) => {
-                    let scope = PassErrorScope::Dispatch {
-                        indirect: false,
-                        pipeline: state.pipeline.last_state,
-                    };
-
-                    fixup_discarded_surfaces(
-                        pending_discard_init_fixups.drain(..),
-                        raw,
-                        &texture_guard,
-                        &mut cmd_buf.trackers.textures,
-                        device,
-                    );
-
-                    state.is_ready().map_pass_err(scope)?;
-                    state
-                        .flush_states(
+                    ComputeCommand::Dispatch(groups) => {
+                        let scope = PassErrorScope::Dispatch {
+                            indirect: false,
+                            pipeline: state.pipeline.last_state,
+                        };
+
+                        fixup_discarded_surfaces(
+                            pending_discard_init_fixups.drain(..),
                             raw,
-                            &mut cmd_buf.trackers,
-                            &*bind_group_guard,
-                            &*buffer_guard,
-                            &*texture_guard,
-                        )
-                        .map_pass_err(scope)?;
+                            &texture_guard,
+                            &mut trackers.textures,
+                            device,
+                        );
 
-                    let groups_size_limit = cmd_buf.limits.max_compute_workgroups_per_dimension;
-
-                    if groups[0] > groups_size_limit
-                        || groups[1] > groups_size_limit
-                        || groups[2] > groups_size_limit
-                    {
-                        return Err(ComputePassErrorInner::Dispatch(
-                            DispatchError::InvalidGroupSize {
-                                current: groups,
-                                limit: groups_size_limit,
-                            },
-                        ))
-                        .map_pass_err(scope);
+                        state.is_ready(limits).map_pass_err(scope)?;
+                        state.count_dispatch().map_pass_err(scope)?;
+                        state.warn_if_pipeline_without_writes();
+                        state
+                            .flush_states(
+                                raw,
+                                trackers,
+                                &*bind_group_guard,
+                                &*buffer_guard,
+                                &*texture_guard,
+                            )
+                            .map_pass_err(scope)?;
+
+                        validate_dispatch_groups(groups, limits)
+                            .map_err(ComputePassErrorInner::Dispatch)
+                            .map_pass_err(scope)?;
+
+                        unsafe {
+                            raw.dispatch(groups);
+                        }
                     }
+                    ComputeCommand::DispatchBatch { count } => {
+                        let scope = PassErrorScope::Dispatch {
+                            indirect: false,
+                            pipeline: state.pipeline.last_state,
+                        };
+                        let batch =
+                            dispatch_batch(base.dispatch_groups, dispatch_group_offset, count)
+                                .map_pass_err(scope)?;
+                        dispatch_group_offset += batch.len();
+
+                        for &groups in batch {
+                            validate_dispatch_groups(groups, limits)
+                                .map_err(ComputePassErrorInner::Dispatch)
+                                .map_pass_err(scope)?;
+                        }
+
+                        fixup_discarded_surfaces(
+                            pending_discard_init_fixups.drain(..),
+                            raw,
+                            &texture_guard,
+                            &mut trackers.textures,
+                            device,
+                        );
 
-                    unsafe {
-                        raw.dispatch(groups);
+                        state.is_ready(limits).map_pass_err(scope)?;
+                        for _ in batch {
+                            state.count_dispatch().map_pass_err(scope)?;
+                        }
+                        state.warn_if_pipeline_without_writes();
+                        state
+                            .flush_states(
+                                raw,
+                                trackers,
+                                &*bind_group_guard,
+                                &*buffer_guard,
+                                &*texture_guard,
+                            )
+                            .map_pass_err(scope)?;
+
+                        for &groups in batch {
+                            unsafe {
+                                raw.dispatch(groups);
+                            }
+                        }
                     }
-                }
-                ComputeCommand::DispatchIndirect { buffer_id, offset } => {
-                    let scope = PassErrorScope::Dispatch {
-                        indirect: true,
-                        pipeline: state.pipeline.last_state,
-                    };
+                    ComputeCommand::DispatchIndirect { buffer_id, offset } => {
+                        let scope = PassErrorScope::Dispatch {
+                            indirect: true,
+                            pipeline: state.pipeline.last_state,
+                        };
+
+                        state.is_ready(limits).map_pass_err(scope)?;
+                        state.count_dispatch().map_pass_err(scope)?;
+                        state.warn_if_pipeline_without_writes();
+
+                        device
+                            .require_downlevel_flags(wgt::DownlevelFlags::INDIRECT_EXECUTION)
+                            .map_pass_err(scope)?;
+
+                        let indirect_buffer = state
+                            .trackers
+                            .buffers
+                            .use_extend(&*buffer_guard, buffer_id, (), hal::BufferUses::INDIRECT)
+                            .map_err(|_| ComputePassErrorInner::InvalidIndirectBuffer(buffer_id))
+                            .map_pass_err(scope)?;
+                        check_buffer_usage(indirect_buffer.usage, wgt::BufferUsages::INDIRECT)
+                            .map_pass_err(scope)?;
+
+                        if offset % 4 != 0 {
+                            return Err(ComputePassErrorInner::UnalignedIndirectBufferOffset(
+                                offset,
+                            ))
+                            .map_pass_err(scope);
+                        }
 
-                    state.is_ready().map_pass_err(scope)?;
+                        let end_offset =
+                            offset + mem::size_of::<wgt::DispatchIndirectArgs>() as u64;
+                        if end_offset > indirect_buffer.size {
+                            return Err(ComputePassErrorInner::IndirectBufferOverrun {
+                                offset,
+                                end_offset,
+                                buffer_size: indirect_buffer.size,
+                            })
+                            .map_pass_err(scope);
+                        }
 
-                    device
-                        .require_downlevel_flags(wgt::DownlevelFlags::INDIRECT_EXECUTION)
-                        .map_pass_err(scope)?;
+                        let buf_raw = indirect_buffer
+                            .raw
+                            .as_ref()
+                            .ok_or(ComputePassErrorInner::InvalidIndirectBuffer(buffer_id))
+                            .map_pass_err(scope)?;
 
-                    let indirect_buffer = state
-                        .trackers
-                        .buffers
-                        .use_extend(&*buffer_guard, buffer_id, (), hal::BufferUses::INDIRECT)
-                        .map_err(|_| ComputePassErrorInner::InvalidIndirectBuffer(buffer_id))
-                        .map_pass_err(scope)?;
-                    check_buffer_usage(indirect_buffer.usage, wgt::BufferUsages::INDIRECT)
-                        .map_pass_err(scope)?;
+                        let stride = 3 * 4; // 3 integers, x/y/z group size
 
-                    let end_offset = offset + mem::size_of::<wgt::DispatchIndirectArgs>() as u64;
-                    if end_offset > indirect_buffer.size {
-                        return Err(ComputePassErrorInner::IndirectBufferOverrun {
-                            offset,
-                            end_offset,
-                            buffer_size: indirect_buffer.size,
-                        })
-                        .map_pass_err(scope);
+                        buffer_memory_init_actions.extend(
+                            indirect_buffer.initialization_status.create_action(
+                                buffer_id,
+                                offset..(offset + stride),
+                                MemoryInitKind::NeedsInitializedMemory,
+                            ),
+                        );
+
+                        state
+                            .flush_states(
+                                raw,
+                                trackers,
+                                &*bind_group_guard,
+                                &*buffer_guard,
+                                &*texture_guard,
+                            )
+                            .map_pass_err(scope)?;
+                        unsafe {
+                            raw.dispatch_indirect(buf_raw, offset);
+                        }
                     }
+                    ComputeCommand::DispatchIndirectCount {
+                        buffer_id,
+                        offset,
+                        count_buffer_id,
+                        count_buffer_offset,
+                        max_count,
+                    } => {
+                        let scope = PassErrorScope::Dispatch {
+                            indirect: true,
+                            pipeline: state.pipeline.last_state,
+                        };
+
+                        state.is_ready(limits).map_pass_err(scope)?;
+                        state.count_dispatch().map_pass_err(scope)?;
+                        state.warn_if_pipeline_without_writes();
+
+                        device
+                            .require_features(wgt::Features::MULTI_DISPATCH_INDIRECT_COUNT)
+                            .map_pass_err(scope)?;
+                        device
+                            .require_downlevel_flags(wgt::DownlevelFlags::INDIRECT_EXECUTION)
+                            .map_pass_err(scope)?;
+
+                        let indirect_buffer = state
+                            .trackers
+                            .buffers
+                            .use_extend(&*buffer_guard, buffer_id, (), hal::BufferUses::INDIRECT)
+                            .map_err(|_| ComputePassErrorInner::InvalidIndirectBuffer(buffer_id))
+                            .map_pass_err(scope)?;
+                        check_buffer_usage(indirect_buffer.usage, wgt::BufferUsages::INDIRECT)
+                            .map_pass_err(scope)?;
+
+                        if offset % 4 != 0 {
+                            return Err(ComputePassErrorInner::UnalignedIndirectBufferOffset(
+                                offset,
+                            ))
+                            .map_pass_err(scope);
+                        }
+                        let indirect_raw = indirect_buffer
+                            .raw
+                            .as_ref()
+                            .ok_or(ComputePassErrorInner::InvalidIndirectBuffer(buffer_id))
+                            .map_pass_err(scope)?;
+
+                        let count_buffer = state
+                            .trackers
+                            .buffers
+                            .use_extend(
+                                &*buffer_guard,
+                                count_buffer_id,
+                                (),
+                                hal::BufferUses::INDIRECT,
+                            )
+                            .map_err(|_| {
+                                ComputePassErrorInner::InvalidIndirectBuffer(count_buffer_id)
+                            })
+                            .map_pass_err(scope)?;
+                        check_buffer_usage(count_buffer.usage, wgt::BufferUsages::INDIRECT)
+                            .map_pass_err(scope)?;
+                        let count_raw = count_buffer
+                            .raw
+                            .as_ref()
+                            .ok_or(ComputePassErrorInner::InvalidIndirectBuffer(
+                                count_buffer_id,
+                            ))
+                            .map_pass_err(scope)?;
+
+                        let stride = mem::size_of::<wgt::DispatchIndirectArgs>() as u64;
+                        let end_offset =
+                            indirect_range_end(offset, stride, max_count, indirect_buffer.size)
+                                .map_pass_err(scope)?;
+                        buffer_memory_init_actions.extend(
+                            indirect_buffer.initialization_status.create_action(
+                                buffer_id,
+                                offset..end_offset,
+                                MemoryInitKind::NeedsInitializedMemory,
+                            ),
+                        );
 
-                    let buf_raw = indirect_buffer
-                        .raw
-                        .as_ref()
-                        .ok_or(ComputePassErrorInner::InvalidIndirectBuffer(buffer_id))
-                        .map_pass_err(scope)?;
+                        let end_count_offset =
+                            count_buffer_range_end(count_buffer_offset, count_buffer.size)
+                                .map_pass_err(scope)?;
+                        buffer_memory_init_actions.extend(
+                            count_buffer.initialization_status.create_action(
+                                count_buffer_id,
+                                count_buffer_offset..end_count_offset,
+                                MemoryInitKind::NeedsInitializedMemory,
+                            ),
+                        );
 
-                    let stride = 3 * 4; // 3 integers, x/y/z group size
+                        state
+                            .flush_states(
+                                raw,
+                                trackers,
+                                &*bind_group_guard,
+                                &*buffer_guard,
+                                &*texture_guard,
+                            )
+                            .map_pass_err(scope)?;
+                        unsafe {
+                            raw.dispatch_indirect_count(
+                                indirect_raw,
+                                offset,
+                                count_raw,
+                                count_buffer_offset,
+                                max_count,
+                            );
+                        }
+                    }
+                    ComputeCommand::DispatchIf {
+                        predicate_buffer_id,
+                        predicate_offset,
+                        groups,
+                    } => {
+                        let scope = PassErrorScope::Dispatch {
+                            indirect: false,
+                            pipeline: state.pipeline.last_state,
+                        };
+
+                        fixup_discarded_surfaces(
+                            pending_discard_init_fixups.drain(..),
+                            raw,
+                            &texture_guard,
+                            &mut trackers.textures,
+                            device,
+                        );
 
-                    cmd_buf.buffer_memory_init_actions.extend(
-                        indirect_buffer.initialization_status.create_action(
-                            buffer_id,
-                            offset..(offset + stride),
-                            MemoryInitKind::NeedsInitializedMemory,
-                        ),
-                    );
+                        state.is_ready(limits).map_pass_err(scope)?;
+                        state.count_dispatch().map_pass_err(scope)?;
+                        state.warn_if_pipeline_without_writes();
+
+                        device
+                            .require_features(wgt::Features::CONDITIONAL_DISPATCH)
+                            .map_pass_err(scope)?;
+
+                        let predicate_buffer = state
+                            .trackers
+                            .buffers
+                            .use_extend(
+                                &*buffer_guard,
+                                predicate_buffer_id,
+                                (),
+                                hal::BufferUses::PREDICATE,
+                            )
+                            .map_err(|_| {
+                                ComputePassErrorInner::InvalidPredicateBuffer(predicate_buffer_id)
+                            })
+                            .map_pass_err(scope)?;
+                        check_buffer_usage(predicate_buffer.usage, wgt::BufferUsages::INDIRECT)
+                            .map_pass_err(scope)?;
+                        let predicate_raw = predicate_buffer
+                            .raw
+                            .as_ref()
+                            .ok_or(ComputePassErrorInner::InvalidPredicateBuffer(
+                                predicate_buffer_id,
+                            ))
+                            .map_pass_err(scope)?;
+
+                        if predicate_offset % 4 != 0 {
+                            return Err(ComputePassErrorInner::UnalignedPredicateOffset(
+                                predicate_offset,
+                            ))
+                            .map_pass_err(scope);
+                        }
+                        let end_offset = predicate_offset + 4;
+                        if end_offset > predicate_buffer.size {
+                            return Err(ComputePassErrorInner::PredicateBufferOverrun {
+                                offset: predicate_offset,
+                                end_offset,
+                                buffer_size: predicate_buffer.size,
+                            })
+                            .map_pass_err(scope);
+                        }
+                        buffer_memory_init_actions.extend(
+                            predicate_buffer.initialization_status.create_action(
+                                predicate_buffer_id,
+                                predicate_offset..end_offset,
+                                MemoryInitKind::NeedsInitializedMemory,
+                            ),
+                        );
 
-                    state
-                        .flush_states(
-                            raw,
-                            &mut cmd_buf.trackers,
-                            &*bind_group_guard,
-                            &*buffer_guard,
-                            &*texture_guard,
-                        )
-                        .map_pass_err(scope)?;
-                    unsafe {
-                        raw.dispatch_indirect(buf_raw, offset);
+                        state
+                            .flush_states(
+                                raw,
+                                trackers,
+                                &*bind_group_guard,
+                                &*buffer_guard,
+                                &*texture_guard,
+                            )
+                            .map_pass_err(scope)?;
+
+                        validate_dispatch_groups(groups, limits)
+                            .map_err(ComputePassErrorInner::Dispatch)
+                            .map_pass_err(scope)?;
+
+                        unsafe {
+                            raw.dispatch_conditional(predicate_raw, predicate_offset, groups);
+                        }
                     }
-                }
-                ComputeCommand::PushDebugGroup { color: _, len } => {
-                    state.debug_scope_depth += 1;
-                    let label =
-                        str::from_utf8(&base.string_data[string_offset..string_offset + len])
-                            .unwrap();
-                    string_offset += len;
-                    unsafe {
-                        raw.begin_debug_marker(label);
+                    ComputeCommand::PushDebugGroup { color, len } => {
+                        let scope = PassErrorScope::PushDebugGroup;
+                        state.debug_scope_depth += 1;
+                        let label = debug_marker_label(base.string_data, string_offset, len)
+                            .ok_or(ComputePassErrorInner::InvalidDebugMarkerLabel)
+                            .map_pass_err(scope)?;
+                        string_offset += len;
+                        if !device.skip_debug_markers {
+                            unsafe {
+                                raw.begin_debug_marker(label, color);
+                            }
+                        }
                     }
-                }
-                ComputeCommand::PopDebugGroup => {
-                    let scope = PassErrorScope::PopDebugGroup;
+                    ComputeCommand::PopDebugGroup => {
+                        let scope = PassErrorScope::PopDebugGroup;
+
+                        if state.debug_scope_depth == 0 {
+                            return Err(ComputePassErrorInner::InvalidPopDebugGroup)
+                                .map_pass_err(scope);
+                        }
+                        state.debug_scope_depth -= 1;
+                        if !device.skip_debug_markers {
+                            unsafe {
+                                raw.end_debug_marker();
+                            }
+                        }
+                    }
+                    ComputeCommand::InsertDebugMarker { color, len } => {
+                        let scope = PassErrorScope::InsertDebugMarker;
+                        let label = debug_marker_label(base.string_data, string_offset, len)
+                            .ok_or(ComputePassErrorInner::InvalidDebugMarkerLabel)
+                            .map_pass_err(scope)?;
+                        string_offset += len;
+                        if !device.skip_debug_markers {
+                            unsafe { raw.insert_debug_marker(label, color) }
+                        }
+                    }
+                    ComputeCommand::WriteTimestamp {
+                        query_set_id,
+                        query_index,
+                    } => {
+                        let scope = PassErrorScope::WriteTimestamp;
+
+                        let query_set = trackers
+                            .query_sets
+                            .use_extend(&*query_set_guard, query_set_id, (), ())
+                            .map_err(|e| match e {
+                                UseExtendError::InvalidResource => {
+                                    ComputePassErrorInner::InvalidQuerySet(query_set_id)
+                                }
+                                _ => unreachable!(),
+                            })
+                            .map_pass_err(scope)?;
+
+                        query_set
+                            .validate_and_write_timestamp(raw, query_set_id, query_index, None)
+                            .map_pass_err(scope)?;
+                    }
+                    ComputeCommand::BeginPipelineStatisticsQuery {
+                        query_set_id,
+                        query_index,
+                    } => {
+                        let scope = PassErrorScope::BeginPipelineStatisticsQuery;
+
+                        let query_set = trackers
+                            .query_sets
+                            .use_extend(&*query_set_guard, query_set_id, (), ())
+                            .map_err(|e| match e {
+                                UseExtendError::InvalidResource => {
+                                    ComputePassErrorInner::InvalidQuerySet(query_set_id)
+                                }
+                                _ => unreachable!(),
+                            })
+                            .map_pass_err(scope)?;
+
+                        query_set
+                            .validate_and_begin_pipeline_statistics_query(
+                                raw,
+                                query_set_id,
+                                query_index,
+                                None,
+                                &mut active_query,
+                            )
+                            .map_pass_err(scope)?;
+                    }
+                    ComputeCommand::EndPipelineStatisticsQuery => {
+                        let scope = PassErrorScope::EndPipelineStatisticsQuery;
 
-                    if state.debug_scope_depth == 0 {
-                        return Err(ComputePassErrorInner::InvalidPopDebugGroup)
+                        end_pipeline_statistics_query(raw, &*query_set_guard, &mut active_query)
+                            .map_pass_err(scope)?;
+                    }
+                    ComputeCommand::BufferBarrier { buffer_id, to } => {
+                        let scope = PassErrorScope::BufferBarrier(buffer_id);
+
+                        let buffer = buffer_guard
+                            .get(buffer_id)
+                            .ok()
+                            .filter(|buffer| buffer.raw.is_some())
+                            .ok_or(ComputePassErrorInner::InvalidBuffer(buffer_id))
+                            .map_pass_err(scope)?;
+                        check_buffer_barrier_usage(buffer.usage, to).map_pass_err(scope)?;
+
+                        // The tracker knows the current usage, so the barrier
+                        // starts from there and the tracked state stays in sync.
+                        let (buffer, pending) = trackers
+                            .buffers
+                            .use_replace(&*buffer_guard, buffer_id, (), to)
+                            .map_err(ComputePassErrorInner::InvalidBuffer)
+                            .map_pass_err(scope)?;
+                        let barriers = pending.map(|pending| pending.into_hal(buffer));
+                        unsafe {
+                            raw.transition_buffers(barriers);
+                        }
+                    }
+                    ComputeCommand::SetPipelineByKey(key) => {
+                        return Err(ComputePassErrorInner::UnresolvedPipelineKey(key))
                             .map_pass_err(scope);
                     }
-                    state.debug_scope_depth -= 1;
-                    unsafe {
-                        raw.end_debug_marker();
+                    ComputeCommand::SetBindGroupByKey { key, .. } => {
+                        return Err(ComputePassErrorInner::UnresolvedBindGroupKey(key))
+                            .map_pass_err(scope);
                     }
-                }
-                ComputeCommand::InsertDebugMarker { color: _, len } => {
-                    let label =
-                        str::from_utf8(&base.string_data[string_offset..string_offset + len])
-                            .unwrap();
-                    string_offset += len;
-                    unsafe { raw.insert_debug_marker(label) }
-                }
-                ComputeCommand::WriteTimestamp {
-                    query_set_id,
-                    query_index,
-                } => {
-                    let scope = PassErrorScope::WriteTimestamp;
-
-                    let query_set = cmd_buf
-                        .trackers
-                        .query_sets
-                        .use_extend(&*query_set_guard, query_set_id, (), ())
-                        .map_err(|e| match e {
-                            UseExtendError::InvalidResource => {
-                                ComputePassErrorInner::InvalidQuerySet(query_set_id)
+                    ComputeCommand::SetBindGroups { .. } => {
+                        unreachable!("bind group batches are expanded before recording")
+                    }
+                    ComputeCommand::PrefetchBuffer {
+                        buffer_id,
+                        offset,
+                        size,
+                    } => {
+                        let scope = PassErrorScope::PrefetchBuffer(buffer_id);
+
+                        let buffer = buffer_guard
+                            .get(buffer_id)
+                            .ok()
+                            .filter(|buffer| buffer.raw.is_some())
+                            .ok_or(ComputePassErrorInner::InvalidBuffer(buffer_id))
+                            .map_pass_err(scope)?;
+                        let end_offset = match size {
+                            Some(size) => offset.checked_add(size.get()),
+                            None => Some(buffer.size),
+                        };
+                        match end_offset {
+                            Some(end_offset)
+                                if offset <= end_offset && end_offset <= buffer.size => {}
+                            end_offset => {
+                                return Err(ComputePassErrorInner::PrefetchRangeOverrun {
+                                    offset,
+                                    end_offset: end_offset.unwrap_or(wgt::BufferAddress::MAX),
+                                    buffer_size: buffer.size,
+                                })
+                                .map_pass_err(scope);
                             }
-                            _ => unreachable!(),
-                        })
-                        .map_pass_err(scope)?;
+                        }
+                        // None of the backends expose a prefetch hint yet, and the
+                        // command has no effect on correctness, so there is nothing to record.
+                    }
+                    ComputeCommand::CopyBufferToBuffer {
+                        src,
+                        src_offset,
+                        dst,
+                        dst_offset,
+                        size,
+                    } => {
+                        let scope = PassErrorScope::CopyBufferToBuffer;
+
+                        if src == dst {
+                            return Err(TransferError::SameSourceDestinationBuffer)
+                                .map_pass_err(scope);
+                        }
+                        if state.debug_scope_depth != 0 {
+                            return Err(ComputePassErrorInner::CopyInsideDebugGroup)
+                                .map_pass_err(scope);
+                        }
+                        if active_query.is_some() {
+                            return Err(ComputePassErrorInner::CopyInsidePipelineStatisticsQuery)
+                                .map_pass_err(scope);
+                        }
 
-                    query_set
-                        .validate_and_write_timestamp(raw, query_set_id, query_index, None)
-                        .map_pass_err(scope)?;
-                }
-                ComputeCommand::BeginPipelineStatisticsQuery {
-                    query_set_id,
-                    query_index,
-                } => {
-                    let scope = PassErrorScope::BeginPipelineStatisticsQuery;
-
-                    let query_set = cmd_buf
-                        .trackers
-                        .query_sets
-                        .use_extend(&*query_set_guard, query_set_id, (), ())
-                        .map_err(|e| match e {
-                            UseExtendError::InvalidResource => {
-                                ComputePassErrorInner::InvalidQuerySet(query_set_id)
-                            }
-                            _ => unreachable!(),
-                        })
-                        .map_pass_err(scope)?;
+                        let (src_buffer, src_pending) = trackers
+                            .buffers
+                            .use_replace(&*buffer_guard, src, (), hal::BufferUses::COPY_SRC)
+                            .map_err(TransferError::InvalidBuffer)
+                            .map_pass_err(scope)?;
+                        let src_raw = src_buffer
+                            .raw
+                            .as_ref()
+                            .ok_or(TransferError::InvalidBuffer(src))
+                            .map_pass_err(scope)?;
+                        if !src_buffer.usage.contains(wgt::BufferUsages::COPY_SRC) {
+                            return Err(TransferError::MissingCopySrcUsageFlag).map_pass_err(scope);
+                        }
+                        // expecting only a single barrier
+                        let src_barrier = src_pending
+                            .map(|pending| pending.into_hal(src_buffer))
+                            .next();
+
+                        let (dst_buffer, dst_pending) = trackers
+                            .buffers
+                            .use_replace(&*buffer_guard, dst, (), hal::BufferUses::COPY_DST)
+                            .map_err(TransferError::InvalidBuffer)
+                            .map_pass_err(scope)?;
+                        let dst_raw = dst_buffer
+                            .raw
+                            .as_ref()
+                            .ok_or(TransferError::InvalidBuffer(dst))
+                            .map_pass_err(scope)?;
+                        if !dst_buffer.usage.contains(wgt::BufferUsages::COPY_DST) {
+                            return Err(TransferError::MissingCopyDstUsageFlag(Some(dst), None))
+                                .map_pass_err(scope);
+                        }
+                        let dst_barrier = dst_pending
+                            .map(|pending| pending.into_hal(dst_buffer))
+                            .next();
 
-                    query_set
-                        .validate_and_begin_pipeline_statistics_query(
-                            raw,
-                            query_set_id,
-                            query_index,
-                            None,
-                            &mut active_query,
+                        if size % wgt::COPY_BUFFER_ALIGNMENT != 0 {
+                            return Err(TransferError::UnalignedCopySize(size)).map_pass_err(scope);
+                        }
+                        for &offset in [src_offset, dst_offset].iter() {
+                            if offset % wgt::COPY_BUFFER_ALIGNMENT != 0 {
+                                return Err(TransferError::UnalignedBufferOffset(offset))
+                                    .map_pass_err(scope);
+                            }
+                        }
+                        let src_end_offset =
+                            copy_range_end(src_offset, size, src_buffer.size, CopySide::Source)
+                                .map_pass_err(scope)?;
+                        let dst_end_offset = copy_range_end(
+                            dst_offset,
+                            size,
+                            dst_buffer.size,
+                            CopySide::Destination,
                         )
                         .map_pass_err(scope)?;
-                }
-                ComputeCommand::EndPipelineStatisticsQuery => {
-                    let scope = PassErrorScope::EndPipelineStatisticsQuery;
 
-                    end_pipeline_statistics_query(raw, &*query_set_guard, &mut active_query)
-                        .map_pass_err(scope)?;
+                        if size == 0 {
+                            log::trace!("Ignoring copy_buffer_to_buffer of size 0");
+                            continue;
+                        }
+
+                        buffer_memory_init_actions.extend(
+                            dst_buffer.initialization_status.create_action(
+                                dst,
+                                dst_offset..dst_end_offset,
+                                MemoryInitKind::ImplicitlyInitialized,
+                            ),
+                        );
+                        buffer_memory_init_actions.extend(
+                            src_buffer.initialization_status.create_action(
+                                src,
+                                src_offset..src_end_offset,
+                                MemoryInitKind::NeedsInitializedMemory,
+                            ),
+                        );
+
+                        let region = hal::BufferCopy {
+                            src_offset,
+                            dst_offset,
+                            size: wgt::BufferSize::new(size).unwrap(),
+                        };
+                        // Copies can't be recorded inside a compute pass on all backends,
+                        // so the raw pass is split around the copy.
+                        unsafe {
+                            raw.end_compute_pass();
+                            raw.transition_buffers(src_barrier.into_iter().chain(dst_barrier));
+                            raw.copy_buffer_to_buffer(src_raw, dst_raw, iter::once(region));
+                            raw.begin_compute_pass(&hal_desc);
+                        }
+                        state
+                            .restore_bound_state(
+                                raw,
+                                &*pipeline_guard,
+                                &*pipeline_layout_guard,
+                                &*bind_group_guard,
+                                base.push_constant_data,
+                            )
+                            .map_pass_err(scope)?;
+                    }
                 }
             }
-        }
+            state.warn_if_undispatched("the pass ended");
+            Ok(())
+        })();
 
+        // The hal pass has to be closed even if recording failed half-way,
+        // otherwise some backends keep the encoder in the compute state.
         unsafe {
             raw.end_compute_pass();
         }
-        cmd_buf.status = CommandEncoderStatus::Recording;
+        result?;
+        *dispatch_count += state.dispatch_count as usize;
 
         // There can be entries left in pending_discard_init_fixups if a bind group was set, but not used (i.e. no Dispatch occurred)
-        // However, we already altered the discard/init_action state on this cmd_buf, so we need to apply the promised changes.
+        // However, we already altered the discard/init_action state on this target, so we need to apply the promised changes.
         fixup_discarded_surfaces(
             pending_discard_init_fixups.into_iter(),
             raw,
             &texture_guard,
-            &mut cmd_buf.trackers.textures,
+            &mut trackers.textures,
             device,
         );
 
@@ -730,7 +2457,7 @@ pub mod compute_ffi {
     use super::{ComputeCommand, ComputePass};
     use crate::{id, RawString};
     use std::{convert::TryInto, ffi, slice};
-    use wgt::{BufferAddress, DynamicOffset};
+    use wgt::{BufferAddress, BufferSize, DynamicOffset};
 
     /// # Safety
     ///
@@ -756,6 +2483,62 @@ pub mod compute_ffi {
         }
     }
 
+    /// Set `group_count` bind groups at consecutive indices starting at `first_index`.
+    ///
+    /// `offsets` contains the dynamic offsets of all groups, one after the other,
+    /// with `offset_lengths` giving the number of offsets of each group.
+    ///
+    /// # Safety
+    ///
+    /// This function is unsafe as there is no guarantee that the given pointers are
+    /// valid for `group_count` elements, and `offsets` for the sum of `offset_lengths`.
+    #[no_mangle]
+    pub unsafe extern "C" fn wgpu_compute_pass_set_bind_groups(
+        pass: &mut ComputePass,
+        first_index: u32,
+        bind_group_ids: *const id::BindGroupId,
+        offset_lengths: *const usize,
+        group_count: usize,
+        offsets: *const DynamicOffset,
+    ) {
+        if group_count == 0 {
+            return;
+        }
+        let bind_group_ids = slice::from_raw_parts(bind_group_ids, group_count);
+        let offset_lengths = slice::from_raw_parts(offset_lengths, group_count);
+        // Values that don't fit into the commands are clamped instead of panicking
+        // across the FFI boundary. Validation rejects the clamped indices and
+        // offset counts, as they are beyond any limit.
+        let clamp = |value: usize| value.min(u8::MAX as usize) as u8;
+        let batch_size = u8::MAX as usize;
+        let mut group_offsets = offsets;
+        for (batch_index, (ids, lengths)) in bind_group_ids
+            .chunks(batch_size)
+            .zip(offset_lengths.chunks(batch_size))
+            .enumerate()
+        {
+            pass.base.commands.push(ComputeCommand::SetBindGroups {
+                first_index: clamp((first_index as usize).saturating_add(batch_index * batch_size)),
+                count: ids.len() as u8,
+            });
+            for (&bind_group_id, &offset_length) in ids.iter().zip(lengths) {
+                let num_dynamic_offsets = clamp(offset_length);
+                pass.base
+                    .bind_groups
+                    .push((bind_group_id, num_dynamic_offsets));
+                if num_dynamic_offsets != 0 {
+                    pass.base
+                        .dynamic_offsets
+                        .extend_from_slice(slice::from_raw_parts(
+                            group_offsets,
+                            num_dynamic_offsets as usize,
+                        ));
+                }
+                group_offsets = group_offsets.wrapping_add(offset_length);
+            }
+        }
+    }
+
     #[no_mangle]
     pub extern "C" fn wgpu_compute_pass_set_pipeline(
         pass: &mut ComputePass,
@@ -766,6 +2549,37 @@ pub mod compute_ffi {
             .push(ComputeCommand::SetPipeline(pipeline_id));
     }
 
+    /// # Safety
+    ///
+    /// This function is unsafe as there is no guarantee that the given pointer is
+    /// valid for `offset_length` elements.
+    #[no_mangle]
+    pub unsafe extern "C" fn wgpu_compute_pass_set_bind_group_by_key(
+        pass: &mut ComputePass,
+        index: u32,
+        key: u64,
+        offsets: *const DynamicOffset,
+        offset_length: usize,
+    ) {
+        pass.base.commands.push(ComputeCommand::SetBindGroupByKey {
+            index: index.try_into().unwrap(),
+            num_dynamic_offsets: offset_length.try_into().unwrap(),
+            key,
+        });
+        if offset_length != 0 {
+            pass.base
+                .dynamic_offsets
+                .extend_from_slice(slice::from_raw_parts(offsets, offset_length));
+        }
+    }
+
+    #[no_mangle]
+    pub extern "C" fn wgpu_compute_pass_set_pipeline_by_key(pass: &mut ComputePass, key: u64) {
+        pass.base
+            .commands
+            .push(ComputeCommand::SetPipelineByKey(key));
+    }
+
     /// # Safety
     ///
     /// This function is unsafe as there is no guarantee that the given pointer is
@@ -817,6 +2631,28 @@ pub mod compute_ffi {
             .push(ComputeCommand::Dispatch([groups_x, groups_y, groups_z]));
     }
 
+    /// # Safety
+    ///
+    /// This function is unsafe as there is no guarantee that the given pointer is
+    /// valid for `dispatch_count` group counts of 3 `u32` each.
+    #[no_mangle]
+    pub unsafe extern "C" fn wgpu_compute_pass_dispatch_batch(
+        pass: &mut ComputePass,
+        groups: *const u32,
+        dispatch_count: u32,
+    ) {
+        pass.base.commands.push(ComputeCommand::DispatchBatch {
+            count: dispatch_count,
+        });
+        if dispatch_count != 0 {
+            pass.base.dispatch_groups.extend(
+                slice::from_raw_parts(groups, dispatch_count as usize * 3)
+                    .chunks_exact(3)
+                    .map(|groups| [groups[0], groups[1], groups[2]]),
+            );
+        }
+    }
+
     #[no_mangle]
     pub extern "C" fn wgpu_compute_pass_dispatch_indirect(
         pass: &mut ComputePass,
@@ -828,6 +2664,42 @@ pub mod compute_ffi {
             .push(ComputeCommand::DispatchIndirect { buffer_id, offset });
     }
 
+    #[no_mangle]
+    pub extern "C" fn wgpu_compute_pass_dispatch_indirect_count(
+        pass: &mut ComputePass,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        count_buffer_id: id::BufferId,
+        count_buffer_offset: BufferAddress,
+        max_count: u32,
+    ) {
+        pass.base
+            .commands
+            .push(ComputeCommand::DispatchIndirectCount {
+                buffer_id,
+                offset,
+                count_buffer_id,
+                count_buffer_offset,
+                max_count,
+            });
+    }
+
+    #[no_mangle]
+    pub extern "C" fn wgpu_compute_pass_dispatch_if(
+        pass: &mut ComputePass,
+        predicate_buffer_id: id::BufferId,
+        predicate_offset: BufferAddress,
+        groups_x: u32,
+        groups_y: u32,
+        groups_z: u32,
+    ) {
+        pass.base.commands.push(ComputeCommand::DispatchIf {
+            predicate_buffer_id,
+            predicate_offset,
+            groups: [groups_x, groups_y, groups_z],
+        });
+    }
+
     /// # Safety
     ///
     /// This function is unsafe as there is no guarantee that the given `label`
@@ -903,4 +2775,529 @@ pub mod compute_ffi {
             .commands
             .push(ComputeCommand::EndPipelineStatisticsQuery);
     }
+
+    /// `to` holds the bits of a `hal::BufferUses`. Unknown bits are ignored.
+    #[no_mangle]
+    pub extern "C" fn wgpu_compute_pass_buffer_barrier(
+        pass: &mut ComputePass,
+        buffer_id: id::BufferId,
+        to: u32,
+    ) {
+        pass.base.commands.push(ComputeCommand::BufferBarrier {
+            buffer_id,
+            to: hal::BufferUses::from_bits_truncate(to),
+        });
+    }
+
+    #[no_mangle]
+    pub extern "C" fn wgpu_compute_pass_prefetch_buffer(
+        pass: &mut ComputePass,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        size: Option<BufferSize>,
+    ) {
+        pass.base.commands.push(ComputeCommand::PrefetchBuffer {
+            buffer_id,
+            offset,
+            size,
+        });
+    }
+
+    #[no_mangle]
+    pub extern "C" fn wgpu_compute_pass_copy_buffer_to_buffer(
+        pass: &mut ComputePass,
+        src: id::BufferId,
+        src_offset: BufferAddress,
+        dst: id::BufferId,
+        dst_offset: BufferAddress,
+        size: BufferAddress,
+    ) {
+        pass.base.commands.push(ComputeCommand::CopyBufferToBuffer {
+            src,
+            src_offset,
+            dst,
+            dst_offset,
+            size,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        check_buffer_barrier_usage, compute_ffi, copy_range_end, count_buffer_range_end,
+        dispatch_batch, expand_bind_group_batches, indirect_dispatch_groups, indirect_range_end,
+        is_read_only_transition, next_dynamic_offsets, set_push_constants,
+        validate_dispatch_groups, ComputeCommand, ComputePass, ComputePassDescriptor,
+        ComputePassErrorInner, DispatchError,
+    };
+    use crate::{
+        binding_model::{PipelineLayout, PushConstantUploadError},
+        command::{CopySide, TransferError},
+        id, LifeGuard, Stored,
+    };
+    use std::ptr;
+
+    #[test]
+    fn indirect_dispatch_groups_from_bytes() {
+        let limits = wgt::Limits::default();
+        let mut data = Vec::new();
+        for group in [4u32, 2, 1] {
+            data.extend_from_slice(&group.to_ne_bytes());
+        }
+        assert_eq!(indirect_dispatch_groups(&data, &limits), Ok([4, 2, 1]));
+        assert_eq!(
+            indirect_dispatch_groups(&data[..8], &limits),
+            Err(DispatchError::InvalidIndirectArgsSize { size: 8 })
+        );
+
+        let too_big = limits.max_compute_workgroups_y + 1;
+        data[4..8].copy_from_slice(&too_big.to_ne_bytes());
+        assert_eq!(
+            indirect_dispatch_groups(&data, &limits),
+            Err(DispatchError::InvalidGroupSize {
+                current: [4, too_big, 1],
+                dimension: 'y',
+                limit: limits.max_compute_workgroups_y,
+            })
+        );
+    }
+
+    #[test]
+    fn bind_group_dynamic_offsets_follow_set_bind_group() {
+        let mut pass = ComputePass::new(id::Id::dummy().0, &ComputePassDescriptor::default());
+        let bind_group_id = id::Id::dummy().0;
+        unsafe {
+            compute_ffi::wgpu_compute_pass_set_bind_group(
+                &mut pass,
+                0,
+                bind_group_id,
+                [256, 512].as_ptr(),
+                2,
+            );
+            compute_ffi::wgpu_compute_pass_set_bind_group(
+                &mut pass,
+                1,
+                bind_group_id,
+                ptr::null(),
+                0,
+            );
+        }
+        compute_ffi::wgpu_compute_pass_dispatch(&mut pass, 1, 1, 1);
+        unsafe {
+            compute_ffi::wgpu_compute_pass_set_bind_group(
+                &mut pass,
+                0,
+                bind_group_id,
+                [768].as_ptr(),
+                1,
+            );
+        }
+
+        assert_eq!(pass.dynamic_offset_count(), 3);
+        let offsets = pass.bind_group_dynamic_offsets().collect::<Vec<_>>();
+        assert_eq!(
+            offsets,
+            [(0, &[256, 512][..]), (1, &[][..]), (0, &[768][..])]
+        );
+        assert_eq!(pass.dynamic_offsets(), [256, 512, 768]);
+        assert!(matches!(
+            pass.commands().last(),
+            Some(ComputeCommand::SetBindGroup {
+                index: 0,
+                num_dynamic_offsets: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn dispatch_count_includes_batches_and_indirect_dispatches() {
+        let mut pass = ComputePass::new(id::Id::dummy().0, &ComputePassDescriptor::default());
+        compute_ffi::wgpu_compute_pass_dispatch(&mut pass, 1, 1, 1);
+        unsafe {
+            compute_ffi::wgpu_compute_pass_dispatch_batch(
+                &mut pass,
+                [1, 1, 1, 2, 2, 2].as_ptr(),
+                2,
+            );
+        }
+        compute_ffi::wgpu_compute_pass_dispatch_indirect(&mut pass, id::Id::dummy().0, 0);
+        compute_ffi::wgpu_compute_pass_set_pipeline(&mut pass, id::Id::dummy().0);
+
+        assert_eq!(pass.dispatch_count(), 4);
+    }
+
+    #[test]
+    fn clear_keeps_pass_memory() {
+        let mut pass = ComputePass::new(id::Id::dummy().0, &ComputePassDescriptor::default());
+        unsafe {
+            compute_ffi::wgpu_compute_pass_set_bind_group(
+                &mut pass,
+                0,
+                id::Id::dummy().0,
+                [256].as_ptr(),
+                1,
+            );
+        }
+        compute_ffi::wgpu_compute_pass_dispatch(&mut pass, 1, 1, 1);
+        let command_capacity = pass.base.commands.capacity();
+
+        pass.clear();
+        assert!(pass.commands().is_empty());
+        assert!(pass.dynamic_offsets().is_empty());
+        assert_eq!(pass.base.commands.capacity(), command_capacity);
+    }
+
+    #[test]
+    fn set_bind_groups_records_a_single_command() {
+        let mut pass = ComputePass::new(id::Id::dummy().0, &ComputePassDescriptor::default());
+        let bind_group_ids = [id::Id::dummy().0; 3];
+        unsafe {
+            compute_ffi::wgpu_compute_pass_set_bind_groups(
+                &mut pass,
+                1,
+                bind_group_ids.as_ptr(),
+                [1, 0, 2].as_ptr(),
+                3,
+                [256, 512, 768].as_ptr(),
+            );
+        }
+
+        assert_eq!(pass.commands().len(), 1);
+        assert!(matches!(
+            pass.commands()[0],
+            ComputeCommand::SetBindGroups {
+                first_index: 1,
+                count: 3,
+            }
+        ));
+        let offsets = pass.bind_group_dynamic_offsets().collect::<Vec<_>>();
+        assert_eq!(
+            offsets,
+            [(1, &[256][..]), (2, &[][..]), (3, &[512, 768][..])]
+        );
+        assert_eq!(pass.dynamic_offsets(), [256, 512, 768]);
+    }
+
+    #[test]
+    fn dispatch_groups_use_per_dimension_limits() {
+        let limits = wgt::Limits {
+            max_compute_workgroups_x: 1024,
+            max_compute_workgroups_y: 16,
+            max_compute_workgroups_z: 1,
+            ..wgt::Limits::default()
+        };
+        assert_eq!(validate_dispatch_groups([1024, 16, 1], &limits), Ok(()));
+        assert_eq!(
+            validate_dispatch_groups([1024, 16, 2], &limits),
+            Err(DispatchError::InvalidGroupSize {
+                current: [1024, 16, 2],
+                dimension: 'z',
+                limit: 1,
+            })
+        );
+
+        // A lowered combined limit still applies to every dimension.
+        let limits = wgt::Limits {
+            max_compute_workgroups_per_dimension: 8,
+            ..limits
+        };
+        assert_eq!(
+            validate_dispatch_groups([9, 1, 1], &limits),
+            Err(DispatchError::InvalidGroupSize {
+                current: [9, 1, 1],
+                dimension: 'x',
+                limit: 8,
+            })
+        );
+    }
+
+    #[test]
+    fn dynamic_offsets_of_rejected_set_bind_group_are_skipped() {
+        // The first command has two offsets, the second one has one.
+        let dynamic_offsets = [256, 512, 768];
+        let mut offset_cursor = 0;
+
+        // The first command is rejected, e.g. for its bind group index being out of range,
+        // after its offsets were taken.
+        next_dynamic_offsets(&dynamic_offsets, &mut offset_cursor, 2);
+        assert_eq!(
+            next_dynamic_offsets(&dynamic_offsets, &mut offset_cursor, 1),
+            [768]
+        );
+        assert_eq!(offset_cursor, dynamic_offsets.len());
+    }
+
+    #[test]
+    fn copy_range_end_rejects_overruns() {
+        assert_eq!(copy_range_end(4, 8, 12, CopySide::Source).unwrap(), 12);
+        assert!(matches!(
+            copy_range_end(8, 8, 12, CopySide::Destination),
+            Err(TransferError::BufferOverrun {
+                start_offset: 8,
+                end_offset: 16,
+                buffer_size: 12,
+                side: CopySide::Destination,
+            })
+        ));
+        // The end of the range doesn't fit into a `u64`.
+        assert!(matches!(
+            copy_range_end(u64::MAX - 3, 8, 12, CopySide::Source),
+            Err(TransferError::BufferOverrun {
+                end_offset: u64::MAX,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn only_read_only_buffer_transitions_are_batched() {
+        use hal::BufferUses as Bu;
+
+        assert!(is_read_only_transition(&(Bu::UNIFORM..Bu::STORAGE_READ)));
+        assert!(is_read_only_transition(
+            &(Bu::STORAGE_READ..Bu::STORAGE_READ | Bu::INDIRECT)
+        ));
+        // Write after write, e.g. between dispatches writing the same storage buffer.
+        assert!(!is_read_only_transition(
+            &(Bu::STORAGE_WRITE..Bu::STORAGE_WRITE)
+        ));
+        // Read after write.
+        assert!(!is_read_only_transition(&(Bu::COPY_DST..Bu::STORAGE_READ)));
+        assert!(!is_read_only_transition(
+            &(Bu::STORAGE_READ | Bu::STORAGE_WRITE..Bu::UNIFORM)
+        ));
+    }
+
+    #[test]
+    fn indirect_range_end_rejects_overruns() {
+        assert_eq!(indirect_range_end(12, 12, 2, 36).unwrap(), 36);
+        assert!(matches!(
+            indirect_range_end(12, 12, 3, 36),
+            Err(ComputePassErrorInner::IndirectBufferOverrun {
+                offset: 12,
+                end_offset: 48,
+                buffer_size: 36,
+            })
+        ));
+        // `offset + stride * max_count` doesn't fit into a `u64`.
+        assert!(matches!(
+            indirect_range_end(u64::MAX - 4, 12, u32::MAX, 36),
+            Err(ComputePassErrorInner::IndirectBufferOverrun {
+                end_offset: u64::MAX,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn count_buffer_range_must_be_aligned() {
+        assert_eq!(count_buffer_range_end(4, 8).unwrap(), 8);
+        assert!(matches!(
+            count_buffer_range_end(2, 8),
+            Err(ComputePassErrorInner::UnalignedIndirectCountBufferOffset(2))
+        ));
+        assert!(matches!(
+            count_buffer_range_end(8, 8),
+            Err(ComputePassErrorInner::IndirectBufferOverrun { .. })
+        ));
+    }
+
+    #[test]
+    fn buffer_barrier_usage_must_be_allowed() {
+        let usage = wgt::BufferUsages::STORAGE | wgt::BufferUsages::INDIRECT;
+        assert!(check_buffer_barrier_usage(usage, hal::BufferUses::INDIRECT).is_ok());
+        assert!(check_buffer_barrier_usage(
+            usage,
+            hal::BufferUses::STORAGE_READ | hal::BufferUses::STORAGE_WRITE
+        )
+        .is_ok());
+        assert!(matches!(
+            check_buffer_barrier_usage(usage, hal::BufferUses::COPY_SRC),
+            Err(ComputePassErrorInner::InvalidBufferBarrierUsage(
+                hal::BufferUses::COPY_SRC
+            ))
+        ));
+        assert!(check_buffer_barrier_usage(usage, hal::BufferUses::empty()).is_err());
+    }
+
+    #[test]
+    fn bind_group_batches_expand_into_single_sets() {
+        let mut pass = ComputePass::new(id::Id::dummy().0, &ComputePassDescriptor::default());
+        let bind_group_ids = [id::Id::dummy().0; 2];
+        compute_ffi::wgpu_compute_pass_dispatch(&mut pass, 1, 1, 1);
+        unsafe {
+            compute_ffi::wgpu_compute_pass_set_bind_groups(
+                &mut pass,
+                2,
+                bind_group_ids.as_ptr(),
+                [0, 1].as_ptr(),
+                2,
+                [256].as_ptr(),
+            );
+        }
+        compute_ffi::wgpu_compute_pass_dispatch(&mut pass, 1, 1, 1);
+
+        let commands = expand_bind_group_batches(pass.base.as_ref()).unwrap();
+        assert!(matches!(
+            *commands,
+            [
+                ComputeCommand::Dispatch(_),
+                ComputeCommand::SetBindGroup {
+                    index: 2,
+                    num_dynamic_offsets: 0,
+                    ..
+                },
+                ComputeCommand::SetBindGroup {
+                    index: 3,
+                    num_dynamic_offsets: 1,
+                    ..
+                },
+                ComputeCommand::Dispatch(_),
+            ]
+        ));
+    }
+
+    #[test]
+    fn bind_group_batch_overrun_is_an_error() {
+        let mut pass = ComputePass::new(id::Id::dummy().0, &ComputePassDescriptor::default());
+        pass.base.commands.push(ComputeCommand::SetBindGroups {
+            first_index: 0,
+            count: 2,
+        });
+        pass.base.bind_groups.push((id::Id::dummy().0, 0));
+
+        assert!(matches!(
+            expand_bind_group_batches(pass.base.as_ref()),
+            Err(ComputePassErrorInner::BindGroupBatchOverrun {
+                count: 2,
+                available: 1,
+            })
+        ));
+    }
+
+    #[test]
+    fn dispatch_batch_overrun_is_an_error() {
+        let dispatch_groups = [[1, 1, 1], [2, 2, 2]];
+
+        assert_eq!(
+            dispatch_batch(&dispatch_groups, 1, 1).unwrap(),
+            &[[2, 2, 2]]
+        );
+        assert!(matches!(
+            dispatch_batch(&dispatch_groups, 1, 2),
+            Err(ComputePassErrorInner::DispatchBatchOverrun {
+                count: 2,
+                available: 1,
+            })
+        ));
+        assert!(matches!(
+            dispatch_batch(&dispatch_groups, usize::MAX, 1),
+            Err(ComputePassErrorInner::DispatchBatchOverrun {
+                count: 1,
+                available: 0,
+            })
+        ));
+    }
+
+    #[test]
+    fn set_bind_groups_clamps_out_of_range_values() {
+        let mut pass = ComputePass::new(id::Id::dummy().0, &ComputePassDescriptor::default());
+        let bind_group_ids = [id::Id::dummy().0; 300];
+        let mut offset_lengths = [0; 300];
+        offset_lengths[0] = 300;
+        unsafe {
+            compute_ffi::wgpu_compute_pass_set_bind_groups(
+                &mut pass,
+                1000,
+                bind_group_ids.as_ptr(),
+                offset_lengths.as_ptr(),
+                300,
+                [0; 300].as_ptr(),
+            );
+        }
+
+        assert!(matches!(
+            *pass.commands(),
+            [
+                ComputeCommand::SetBindGroups {
+                    first_index: 255,
+                    count: 255,
+                },
+                ComputeCommand::SetBindGroups {
+                    first_index: 255,
+                    count: 45,
+                },
+            ]
+        ));
+        assert_eq!(pass.base.bind_groups[0].1, 255);
+        assert_eq!(pass.dynamic_offsets().len(), 255);
+        assert!(expand_bind_group_batches(pass.base.as_ref()).is_ok());
+    }
+
+    #[test]
+    fn set_push_constants_rejects_out_of_range_values() {
+        let instance_desc = hal::InstanceDescriptor {
+            name: "test",
+            flags: hal::InstanceFlags::empty(),
+        };
+        let device: <hal::api::Empty as hal::Api>::Device =
+            unsafe { hal::Instance::init(&instance_desc).unwrap() };
+        let mut encoder = unsafe {
+            hal::Device::create_command_encoder(
+                &device,
+                &hal::CommandEncoderDescriptor {
+                    label: None,
+                    queue: &device,
+                },
+            )
+            .unwrap()
+        };
+        let push_constant_ranges = [wgt::PushConstantRange {
+            stages: wgt::ShaderStages::COMPUTE,
+            range: 0..8,
+        }];
+        let raw = unsafe {
+            hal::Device::create_pipeline_layout(
+                &device,
+                &hal::PipelineLayoutDescriptor {
+                    label: None,
+                    flags: hal::PipelineLayoutFlags::empty(),
+                    bind_group_layouts: &[],
+                    push_constant_ranges: &push_constant_ranges,
+                },
+            )
+            .unwrap()
+        };
+        let device_life_guard = LifeGuard::new("device");
+        let layout = PipelineLayout::<hal::api::Empty> {
+            raw,
+            device_id: Stored {
+                value: id::Id::dummy(),
+                ref_count: device_life_guard.add_ref(),
+            },
+            life_guard: LifeGuard::new("layout"),
+            bind_group_layout_ids: Default::default(),
+            push_constant_ranges: push_constant_ranges.iter().cloned().collect(),
+        };
+        let data = [0; 2];
+
+        assert!(set_push_constants(&mut encoder, &layout, &data, 0, 8, 0).is_ok());
+        assert!(matches!(
+            set_push_constants(&mut encoder, &layout, &data, u32::MAX - 3, 8, 0),
+            Err(PushConstantUploadError::Overflow {
+                offset: 0xFFFF_FFFC,
+                size_bytes: 8,
+            })
+        ));
+        assert!(matches!(
+            set_push_constants(&mut encoder, &layout, &data, 0, 8, 1),
+            Err(PushConstantUploadError::ValuesOverrun {
+                start: 1,
+                end: 3,
+                available: 2,
+            })
+        ));
+    }
 }
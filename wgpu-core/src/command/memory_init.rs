@@ -62,6 +62,8 @@ impl CommandBufferTextureMemoryActions {
         // But that would mean splitting up the action which is more than we'd win here.
         self.init_actions
             .extend(match texture_guard.get(action.id) {
+                // External textures are initialized by their source.
+                Ok(texture) if texture.external => None,
                 Ok(texture) => texture.initialization_status.check_action(action),
                 Err(_) => return immediately_necessary_clears, // texture no longer exists
             });
@@ -151,6 +153,7 @@ pub(crate) fn fixup_discarded_surfaces<
         collect_zero_buffer_copies_for_clear_texture(
             &texture.desc,
             device.alignments.buffer_copy_pitch.get() as u32,
+            device.zero_buffer_size,
             mip_range,
             layer_range,
             &mut zero_buffer_copy_regions,
@@ -322,6 +325,7 @@ impl<A: hal::Api> BakedCommands<A> {
                         collect_zero_buffer_copies_for_clear_texture(
                             &texture.desc,
                             device.alignments.buffer_copy_pitch.get() as u32,
+                            device.zero_buffer_size,
                             range.mip_range.clone(),
                             range.layer_range.clone(),
                             &mut zero_buffer_copy_regions,
@@ -352,6 +356,9 @@ impl<A: hal::Api> BakedCommands<A> {
             let texture = texture_guard
                 .get_mut(surface_discard.texture)
                 .map_err(|_| DestroyedTextureError(surface_discard.texture))?;
+            if texture.external {
+                continue;
+            }
             texture
                 .initialization_status
                 .discard(surface_discard.mip_level, surface_discard.layer);
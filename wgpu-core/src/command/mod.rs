@@ -10,6 +10,7 @@ mod transfer;
 
 pub use self::bundle::*;
 pub(crate) use self::clear::collect_zero_buffer_copies_for_clear_texture;
+use self::clear::RecentTextureClear;
 pub use self::compute::*;
 pub use self::draw::*;
 use self::memory_init::CommandBufferTextureMemoryActions;
@@ -30,6 +31,8 @@ use crate::{
 use hal::CommandEncoder as _;
 use thiserror::Error;
 
+use std::mem;
+
 const PUSH_CONSTANT_CLEAR_ARRAY: &[u32] = &[0_u32; 64];
 
 #[derive(Debug)]
@@ -84,6 +87,10 @@ pub struct CommandBuffer<A: hal::Api> {
     pub(crate) trackers: TrackerSet,
     buffer_memory_init_actions: Vec<BufferInitTrackerAction>,
     texture_memory_actions: CommandBufferTextureMemoryActions,
+    /// Texture clears recorded since the last command that wasn't a texture clear.
+    recent_texture_clears: Vec<RecentTextureClear>,
+    /// Number of dispatches of all compute passes recorded so far.
+    dispatch_count: usize,
     limits: wgt::Limits,
     support_clear_buffer_texture: bool,
     #[cfg(feature = "trace")]
@@ -112,6 +119,8 @@ impl<A: HalApi> CommandBuffer<A> {
             trackers: TrackerSet::new(A::VARIANT),
             buffer_memory_init_actions: Default::default(),
             texture_memory_actions: Default::default(),
+            recent_texture_clears: Vec::new(),
+            dispatch_count: 0,
             limits,
             support_clear_buffer_texture: features.contains(wgt::Features::CLEAR_COMMANDS),
             #[cfg(feature = "trace")]
@@ -123,6 +132,32 @@ impl<A: HalApi> CommandBuffer<A> {
         }
     }
 
+    /// Discard everything recorded so far, and make the command buffer
+    /// ready for recording again, keeping its hal encoder.
+    ///
+    /// Returns the trackers of the discarded commands, which the caller has
+    /// to untrack from the device so that resources dropped in the meantime
+    /// are still freed.
+    pub(crate) fn reset_for_reuse(&mut self) -> TrackerSet {
+        unsafe {
+            if self.encoder.is_open {
+                self.encoder.is_open = false;
+                self.encoder.raw.discard_encoding();
+            }
+            self.encoder.raw.reset_all(self.encoder.list.drain(..));
+        }
+        self.status = CommandEncoderStatus::Recording;
+        self.buffer_memory_init_actions.clear();
+        self.texture_memory_actions = Default::default();
+        self.recent_texture_clears.clear();
+        self.dispatch_count = 0;
+        #[cfg(feature = "trace")]
+        if let Some(ref mut commands) = self.commands {
+            commands.clear();
+        }
+        mem::replace(&mut self.trackers, TrackerSet::new(A::VARIANT))
+    }
+
     pub(crate) fn insert_barriers(
         raw: &mut A::CommandEncoder,
         base: &mut TrackerSet,
@@ -154,6 +189,18 @@ impl<A: hal::Api> CommandBuffer<A> {
     fn get_encoder_mut(
         storage: &mut Storage<Self, id::CommandEncoderId>,
         id: id::CommandEncoderId,
+    ) -> Result<&mut Self, CommandEncoderError> {
+        let cmd_buf = Self::get_encoder_mut_keep_clears(storage, id)?;
+        // Whatever the caller records may write to the recently cleared textures.
+        cmd_buf.recent_texture_clears.clear();
+        Ok(cmd_buf)
+    }
+
+    /// Like [`Self::get_encoder_mut`], but keeps the recent texture clears around.
+    /// Only to be used for recording texture clears.
+    fn get_encoder_mut_keep_clears(
+        storage: &mut Storage<Self, id::CommandEncoderId>,
+        id: id::CommandEncoderId,
     ) -> Result<&mut Self, CommandEncoderError> {
         match storage.get_mut(id) {
             Ok(cmd_buf) => match cmd_buf.status {
@@ -202,6 +249,8 @@ pub struct BasePassRef<'a, C> {
     pub dynamic_offsets: &'a [wgt::DynamicOffset],
     pub string_data: &'a [u8],
     pub push_constant_data: &'a [u32],
+    pub dispatch_groups: &'a [[u32; 3]],
+    pub bind_groups: &'a [(id::BindGroupId, u8)],
 }
 
 #[doc(hidden)]
@@ -220,6 +269,8 @@ pub struct BasePass<C> {
     pub dynamic_offsets: Vec<wgt::DynamicOffset>,
     pub string_data: Vec<u8>,
     pub push_constant_data: Vec<u32>,
+    pub dispatch_groups: Vec<[u32; 3]>,
+    pub bind_groups: Vec<(id::BindGroupId, u8)>,
 }
 
 impl<C: Clone> BasePass<C> {
@@ -230,6 +281,8 @@ impl<C: Clone> BasePass<C> {
             dynamic_offsets: Vec::new(),
             string_data: Vec::new(),
             push_constant_data: Vec::new(),
+            dispatch_groups: Vec::new(),
+            bind_groups: Vec::new(),
         }
     }
 
@@ -241,9 +294,21 @@ impl<C: Clone> BasePass<C> {
             dynamic_offsets: base.dynamic_offsets.to_vec(),
             string_data: base.string_data.to_vec(),
             push_constant_data: base.push_constant_data.to_vec(),
+            dispatch_groups: base.dispatch_groups.to_vec(),
+            bind_groups: base.bind_groups.to_vec(),
         }
     }
 
+    /// Remove all recorded commands and their data, keeping the allocated memory.
+    fn clear(&mut self) {
+        self.commands.clear();
+        self.dynamic_offsets.clear();
+        self.string_data.clear();
+        self.push_constant_data.clear();
+        self.dispatch_groups.clear();
+        self.bind_groups.clear();
+    }
+
     pub fn as_ref(&self) -> BasePassRef<C> {
         BasePassRef {
             label: self.label.as_deref(),
@@ -251,6 +316,8 @@ impl<C: Clone> BasePass<C> {
             dynamic_offsets: &self.dynamic_offsets,
             string_data: &self.string_data,
             push_constant_data: &self.push_constant_data,
+            dispatch_groups: &self.dispatch_groups,
+            bind_groups: &self.bind_groups,
         }
     }
 }
@@ -297,6 +364,49 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         (encoder_id, error)
     }
 
+    /// Discard all commands recorded into `encoder_id`, so that it can be
+    /// recorded into again, even after an error.
+    ///
+    /// Finished command buffers can't be reset, as they may have been submitted.
+    pub fn command_encoder_reset<A: HalApi>(
+        &self,
+        encoder_id: id::CommandEncoderId,
+    ) -> Result<(), CommandEncoderError> {
+        profiling::scope!("reset", "CommandEncoder");
+
+        let hub = A::hub(self);
+        let mut token = Token::root();
+
+        let (mut device_guard, mut token) = hub.devices.write(&mut token);
+        let (device_id, trackers) = {
+            let (mut cmd_buf_guard, _) = hub.command_buffers.write(&mut token);
+            let cmd_buf = cmd_buf_guard
+                .get_mut(encoder_id)
+                .map_err(|_| CommandEncoderError::Invalid)?;
+            if let CommandEncoderStatus::Finished = cmd_buf.status {
+                return Err(CommandEncoderError::NotRecording);
+            }
+            (cmd_buf.device_id.value, cmd_buf.reset_for_reuse())
+        };
+        device_guard[device_id].untrack::<G>(hub, &trackers, &mut token);
+
+        Ok(())
+    }
+
+    /// Total number of dispatches of the compute passes run on `encoder_id`.
+    pub fn command_encoder_dispatch_count<A: HalApi>(
+        &self,
+        encoder_id: id::CommandEncoderId,
+    ) -> Result<usize, CommandEncoderError> {
+        let hub = A::hub(self);
+        let mut token = Token::root();
+        let (cmd_buf_guard, _) = hub.command_buffers.read(&mut token);
+        cmd_buf_guard
+            .get(encoder_id)
+            .map(|cmd_buf| cmd_buf.dispatch_count)
+            .map_err(|_| CommandEncoderError::Invalid)
+    }
+
     pub fn command_encoder_push_debug_group<A: HalApi>(
         &self,
         encoder_id: id::CommandEncoderId,
@@ -312,7 +422,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let cmd_buf_raw = cmd_buf.encoder.open();
 
         unsafe {
-            cmd_buf_raw.begin_debug_marker(label);
+            cmd_buf_raw.begin_debug_marker(label, 0);
         }
         Ok(())
     }
@@ -332,7 +442,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let cmd_buf_raw = cmd_buf.encoder.open();
 
         unsafe {
-            cmd_buf_raw.insert_debug_marker(label);
+            cmd_buf_raw.insert_debug_marker(label, 0);
         }
         Ok(())
     }
@@ -377,6 +487,15 @@ where
     }
 }
 
+/// Reads the label of a debug marker from the string data of a pass.
+///
+/// Returns `None` if the label is out of bounds or not valid UTF-8,
+/// which can happen with labels passed in through the FFI.
+fn debug_marker_label(string_data: &[u8], offset: usize, len: usize) -> Option<&str> {
+    let bytes = string_data.get(offset..offset.checked_add(len)?)?;
+    std::str::from_utf8(bytes).ok()
+}
+
 #[derive(Debug)]
 struct StateChange<T> {
     last_state: Option<T>,
@@ -446,8 +565,18 @@ pub enum PassErrorScope {
         indirect: bool,
         pipeline: Option<id::ComputePipelineId>,
     },
+    #[error("In a push_debug_group command")]
+    PushDebugGroup,
     #[error("In a pop_debug_group command")]
     PopDebugGroup,
+    #[error("In a insert_debug_marker command")]
+    InsertDebugMarker,
+    #[error("In a buffer_barrier command")]
+    BufferBarrier(id::BufferId),
+    #[error("In a prefetch_buffer command")]
+    PrefetchBuffer(id::BufferId),
+    #[error("In a copy_buffer_to_buffer command")]
+    CopyBufferToBuffer,
 }
 
 impl PrettyError for PassErrorScope {
@@ -472,6 +601,12 @@ impl PrettyError for PassErrorScope {
             Self::SetIndexBuffer(id) => {
                 fmt.buffer_label(&id);
             }
+            Self::BufferBarrier(id) => {
+                fmt.buffer_label(&id);
+            }
+            Self::PrefetchBuffer(id) => {
+                fmt.buffer_label(&id);
+            }
             Self::Draw {
                 pipeline: Some(id), ..
             } => {
@@ -486,3 +621,71 @@ impl PrettyError for PassErrorScope {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{debug_marker_label, CommandBuffer};
+    use crate::{id, LifeGuard, Stored};
+
+    #[test]
+    fn debug_marker_label_rejects_invalid_utf8() {
+        let string_data = b"group\xff\xfemarker";
+        assert_eq!(debug_marker_label(string_data, 0, 5), Some("group"));
+        assert_eq!(debug_marker_label(string_data, 5, 2), None);
+        assert_eq!(debug_marker_label(string_data, 7, 6), Some("marker"));
+        assert_eq!(debug_marker_label(string_data, 7, 7), None);
+    }
+
+    #[test]
+    fn reset_for_reuse_releases_tracked_resources() {
+        let instance_desc = hal::InstanceDescriptor {
+            name: "test",
+            flags: hal::InstanceFlags::empty(),
+        };
+        let device: <hal::api::Empty as hal::Api>::Device =
+            unsafe { hal::Instance::init(&instance_desc).unwrap() };
+        let encoder = unsafe {
+            hal::Device::create_command_encoder(
+                &device,
+                &hal::CommandEncoderDescriptor {
+                    label: None,
+                    queue: &device,
+                },
+            )
+            .unwrap()
+        };
+        let device_life_guard = LifeGuard::new("device");
+        let mut cmd_buf = CommandBuffer::<hal::api::Empty>::new(
+            encoder,
+            Stored {
+                value: id::Id::dummy(),
+                ref_count: device_life_guard.add_ref(),
+            },
+            wgt::Limits::default(),
+            wgt::DownlevelCapabilities::default(),
+            wgt::Features::empty(),
+            #[cfg(feature = "trace")]
+            false,
+            &None,
+        );
+
+        let buffer_id = id::Id::dummy();
+        let buffer_life_guard = LifeGuard::new("buffer");
+        let buffer_ref_count = buffer_life_guard.add_ref();
+        cmd_buf
+            .trackers
+            .buffers
+            .change_extend(buffer_id, &buffer_ref_count, (), hal::BufferUses::COPY_SRC)
+            .unwrap();
+        // the life guard, `buffer_ref_count` and the tracker
+        assert_eq!(buffer_ref_count.load(), 3);
+
+        let trackers = cmd_buf.reset_for_reuse();
+        assert!(cmd_buf.trackers.buffers.is_empty());
+        assert_eq!(trackers.buffers.used().collect::<Vec<_>>(), [buffer_id]);
+
+        // Once the device untracked the discarded commands, nothing keeps the buffer alive.
+        drop(trackers);
+        assert_eq!(buffer_ref_count.load(), 2);
+    }
+}
@@ -98,6 +98,50 @@ impl From<wgt::QueryType> for SimplifiedQueryType {
     }
 }
 
+/// Resolved result of a single pipeline statistics query.
+///
+/// Statistics that weren't requested are `None`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PipelineStatistics {
+    pub vertex_shader_invocations: Option<u64>,
+    pub clipper_invocations: Option<u64>,
+    pub clipper_primitives_out: Option<u64>,
+    pub fragment_shader_invocations: Option<u64>,
+    pub compute_shader_invocations: Option<u64>,
+}
+
+impl PipelineStatistics {
+    /// Interprets the values of one query resolved into a buffer.
+    ///
+    /// `bytes` starts at the first value of the query. Values are written in
+    /// the order the flags are declared in [`wgt::PipelineStatisticsTypes`],
+    /// which all backends follow. Statistics whose value lies past the end of
+    /// `bytes` are `None`.
+    pub fn from_resolved_bytes(flags: wgt::PipelineStatisticsTypes, bytes: &[u8]) -> Self {
+        use wgt::PipelineStatisticsTypes as Pst;
+
+        let mut values = bytes.chunks_exact(8).map(|chunk| {
+            let mut value = [0; 8];
+            value.copy_from_slice(chunk);
+            u64::from_ne_bytes(value)
+        });
+        let mut next_value = |flag| {
+            if flags.contains(flag) {
+                values.next()
+            } else {
+                None
+            }
+        };
+        Self {
+            vertex_shader_invocations: next_value(Pst::VERTEX_SHADER_INVOCATIONS),
+            clipper_invocations: next_value(Pst::CLIPPER_INVOCATIONS),
+            clipper_primitives_out: next_value(Pst::CLIPPER_PRIMITIVES_OUT),
+            fragment_shader_invocations: next_value(Pst::FRAGMENT_SHADER_INVOCATIONS),
+            compute_shader_invocations: next_value(Pst::COMPUTE_SHADER_INVOCATIONS),
+        }
+    }
+}
+
 /// Error encountered when dealing with queries
 #[derive(Clone, Debug, Error)]
 pub enum QueryError {
@@ -420,3 +464,40 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::PipelineStatistics;
+    use wgt::PipelineStatisticsTypes as Pst;
+
+    #[test]
+    fn pipeline_statistics_from_resolved_bytes() {
+        let bytes = [7u64, 42]
+            .iter()
+            .flat_map(|value| value.to_ne_bytes())
+            .collect::<Vec<_>>();
+
+        let stats = PipelineStatistics::from_resolved_bytes(
+            Pst::CLIPPER_PRIMITIVES_OUT | Pst::COMPUTE_SHADER_INVOCATIONS,
+            &bytes,
+        );
+        assert_eq!(
+            stats,
+            PipelineStatistics {
+                clipper_primitives_out: Some(7),
+                compute_shader_invocations: Some(42),
+                ..Default::default()
+            }
+        );
+
+        let stats = PipelineStatistics::from_resolved_bytes(Pst::all(), &bytes);
+        assert_eq!(
+            stats,
+            PipelineStatistics {
+                vertex_shader_invocations: Some(7),
+                clipper_invocations: Some(42),
+                ..Default::default()
+            }
+        );
+    }
+}
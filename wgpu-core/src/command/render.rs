@@ -2,7 +2,7 @@ use crate::{
     binding_model::BindError,
     command::{
         bind::Binder,
-        end_pipeline_statistics_query,
+        debug_marker_label, end_pipeline_statistics_query,
         memory_init::{fixup_discarded_surfaces, SurfacesInDiscardState},
         BasePass, BasePassRef, CommandBuffer, CommandEncoderError, CommandEncoderStatus, DrawError,
         ExecutionError, MapPassErr, PassErrorScope, QueryResetMap, QueryUseError, RenderCommand,
@@ -38,7 +38,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::track::UseExtendError;
-use std::{borrow::Cow, fmt, iter, marker::PhantomData, mem, num::NonZeroU32, ops::Range, str};
+use std::{borrow::Cow, fmt, iter, marker::PhantomData, mem, num::NonZeroU32, ops::Range};
 
 use super::{memory_init::TextureSurfaceDiscard, CommandBufferTextureMemoryActions};
 
@@ -447,6 +447,8 @@ pub enum RenderPassErrorInner {
     },
     #[error("cannot pop debug group, because number of pushed debug groups is zero")]
     InvalidPopDebugGroup,
+    #[error("debug marker label is out of bounds or not valid UTF-8")]
+    InvalidDebugMarkerLabel,
     #[error(transparent)]
     ResourceUsageConflict(#[from] UsageConflict),
     #[error("render bundle has incompatible targets, {0}")]
@@ -1688,14 +1690,15 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             },
                         }
                     }
-                    RenderCommand::PushDebugGroup { color: _, len } => {
+                    RenderCommand::PushDebugGroup { color, len } => {
+                        let scope = PassErrorScope::PushDebugGroup;
                         state.debug_scope_depth += 1;
-                        let label =
-                            str::from_utf8(&base.string_data[string_offset..string_offset + len])
-                                .unwrap();
+                        let label = debug_marker_label(base.string_data, string_offset, len)
+                            .ok_or(RenderPassErrorInner::InvalidDebugMarkerLabel)
+                            .map_pass_err(scope)?;
                         string_offset += len;
                         unsafe {
-                            raw.begin_debug_marker(label);
+                            raw.begin_debug_marker(label, color);
                         }
                     }
                     RenderCommand::PopDebugGroup => {
@@ -1709,13 +1712,14 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             raw.end_debug_marker();
                         }
                     }
-                    RenderCommand::InsertDebugMarker { color: _, len } => {
-                        let label =
-                            str::from_utf8(&base.string_data[string_offset..string_offset + len])
-                                .unwrap();
+                    RenderCommand::InsertDebugMarker { color, len } => {
+                        let scope = PassErrorScope::InsertDebugMarker;
+                        let label = debug_marker_label(base.string_data, string_offset, len)
+                            .ok_or(RenderPassErrorInner::InvalidDebugMarkerLabel)
+                            .map_pass_err(scope)?;
                         string_offset += len;
                         unsafe {
-                            raw.insert_debug_marker(label);
+                            raw.insert_debug_marker(label, color);
                         }
                     }
                     RenderCommand::WriteTimestamp {
@@ -651,6 +651,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             collect_zero_buffer_copies_for_clear_texture(
                 &dst_texture.desc,
                 device.alignments.buffer_copy_pitch.get() as u32,
+                device.zero_buffer_size,
                 immediate_init.mip_level..(immediate_init.mip_level + 1),
                 immediate_init.layer..(immediate_init.layer + 1),
                 &mut dst_zero_buffer_copy_regions,
@@ -938,6 +939,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             collect_zero_buffer_copies_for_clear_texture(
                 &dst_texture.desc,
                 device.alignments.buffer_copy_pitch.get() as u32,
+                device.zero_buffer_size,
                 immediate_init.mip_level..(immediate_init.mip_level + 1),
                 immediate_init.layer..(immediate_init.layer + 1),
                 &mut dst_zero_buffer_copy_regions,
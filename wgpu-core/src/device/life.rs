@@ -89,6 +89,7 @@ struct NonReferencedResources<A: hal::Api> {
     // Note: we keep the associated ID here in order to be able to check
     // at any point what resources are used in a submission.
     texture_views: Vec<(id::Valid<id::TextureViewId>, A::TextureView)>,
+    texture_clear_views: Vec<A::TextureView>,
     samplers: Vec<A::Sampler>,
     bind_groups: Vec<A::BindGroup>,
     compute_pipes: Vec<A::ComputePipeline>,
@@ -104,6 +105,7 @@ impl<A: hal::Api> NonReferencedResources<A> {
             buffers: Vec::new(),
             textures: Vec::new(),
             texture_views: Vec::new(),
+            texture_clear_views: Vec::new(),
             samplers: Vec::new(),
             bind_groups: Vec::new(),
             compute_pipes: Vec::new(),
@@ -118,6 +120,7 @@ impl<A: hal::Api> NonReferencedResources<A> {
         self.buffers.extend(other.buffers);
         self.textures.extend(other.textures);
         self.texture_views.extend(other.texture_views);
+        self.texture_clear_views.extend(other.texture_clear_views);
         self.samplers.extend(other.samplers);
         self.bind_groups.extend(other.bind_groups);
         self.compute_pipes.extend(other.compute_pipes);
@@ -134,6 +137,12 @@ impl<A: hal::Api> NonReferencedResources<A> {
                 device.destroy_buffer(raw);
             }
         }
+        if !self.texture_clear_views.is_empty() {
+            profiling::scope!("destroy_texture_clear_views");
+            for raw in self.texture_clear_views.drain(..) {
+                device.destroy_texture_view(raw);
+            }
+        }
         if !self.textures.is_empty() {
             profiling::scope!("destroy_textures");
             for raw in self.textures.drain(..) {
@@ -258,7 +267,10 @@ impl<A: hal::Api> LifetimeTracker<A> {
         for res in temp_resources {
             match res {
                 TempResource::Buffer(raw) => last_resources.buffers.push(raw),
-                TempResource::Texture(raw) => last_resources.textures.push(raw),
+                TempResource::Texture(raw, clear_views) => {
+                    last_resources.textures.push(raw);
+                    last_resources.texture_clear_views.extend(clear_views);
+                }
             }
         }
 
@@ -338,7 +350,10 @@ impl<A: hal::Api> LifetimeTracker<A> {
             .map_or(&mut self.free_resources, |a| &mut a.last_resources);
         match temp_resource {
             TempResource::Buffer(raw) => resources.buffers.push(raw),
-            TempResource::Texture(raw) => resources.textures.push(raw),
+            TempResource::Texture(raw, clear_views) => {
+                resources.textures.push(raw);
+                resources.texture_clear_views.extend(clear_views);
+            }
         }
     }
 
@@ -457,12 +472,15 @@ impl<A: HalApi> LifetimeTracker<A> {
                             resource::TextureInner::Native { raw: Some(raw) } => raw,
                             _ => continue,
                         };
-                        self.active
+                        let non_referenced_resources = self
+                            .active
                             .iter_mut()
                             .find(|a| a.index == submit_index)
-                            .map_or(&mut self.free_resources, |a| &mut a.last_resources)
-                            .textures
-                            .push(raw);
+                            .map_or(&mut self.free_resources, |a| &mut a.last_resources);
+                        non_referenced_resources.textures.push(raw);
+                        non_referenced_resources
+                            .texture_clear_views
+                            .extend(res.clear_views);
                     }
                 }
             }
@@ -30,6 +30,7 @@ pub mod queue;
 pub mod trace;
 
 pub const SHADER_STAGE_COUNT: usize = 3;
+// Default size of the zero buffer, if `DeviceDescriptor::zero_buffer_size` isn't set.
 // Should be large enough for the largest possible texture row. This value is enough for a 16k texture with float4 format.
 pub(crate) const ZERO_BUFFER_SIZE: BufferAddress = 512 << 10;
 
@@ -250,6 +251,7 @@ pub struct Device<A: hal::Api> {
     pub(crate) adapter_id: Stored<id::AdapterId>,
     pub(crate) queue: A::Queue,
     pub(crate) zero_buffer: A::Buffer,
+    pub(crate) zero_buffer_size: BufferAddress,
     //pub(crate) cmd_allocator: command::CommandAllocator<A>,
     //mem_allocator: Mutex<alloc::MemoryAllocator<A>>,
     //desc_allocator: Mutex<descriptor::DescriptorAllocator<A>>,
@@ -267,6 +269,14 @@ pub struct Device<A: hal::Api> {
     pub(crate) limits: wgt::Limits,
     pub(crate) features: wgt::Features,
     pub(crate) downlevel: wgt::DownlevelCapabilities,
+    /// Clear sRGB textures with a render pass, see `hal::Capabilities`.
+    pub(crate) srgb_clear_needs_render_pass: bool,
+    /// Skip the debug markers of compute passes when running them.
+    pub(crate) skip_debug_markers: bool,
+    /// Warn about compute passes that set up a dispatch but never issue it.
+    pub(crate) warn_missing_dispatch: bool,
+    /// Warn about compute dispatches of pipelines that write to no resource.
+    pub(crate) warn_pipeline_without_writes: bool,
     //TODO: move this behind another mutex. This would allow several methods to switch
     // to borrow Device immutably, such as `write_buffer`, `write_texture`, and `buffer_unmap`.
     pending_writes: queue::PendingWrites<A>,
@@ -310,6 +320,7 @@ impl<A: HalApi> Device<A> {
         adapter_id: Stored<id::AdapterId>,
         alignments: hal::Alignments,
         downlevel: wgt::DownlevelCapabilities,
+        srgb_clear_needs_render_pass: bool,
         desc: &DeviceDescriptor,
         trace_path: Option<&std::path::Path>,
     ) -> Result<Self, CreateDeviceError> {
@@ -329,11 +340,14 @@ impl<A: HalApi> Device<A> {
         let mut pending_writes = queue::PendingWrites::<A>::new(pending_encoder);
 
         // Create zeroed buffer used for texture clears.
+        let zero_buffer_size = desc
+            .zero_buffer_size
+            .map_or(ZERO_BUFFER_SIZE, |size| size.get());
         let zero_buffer = unsafe {
             open.device
                 .create_buffer(&hal::BufferDescriptor {
                     label: Some("wgpu zero init buffer"),
-                    size: ZERO_BUFFER_SIZE,
+                    size: zero_buffer_size,
                     usage: hal::BufferUses::COPY_SRC | hal::BufferUses::COPY_DST,
                     memory_flags: hal::MemoryFlags::empty(),
                 })
@@ -349,7 +363,7 @@ impl<A: HalApi> Device<A> {
                 }));
             pending_writes
                 .command_encoder
-                .clear_buffer(&zero_buffer, 0..ZERO_BUFFER_SIZE);
+                .clear_buffer(&zero_buffer, 0..zero_buffer_size);
             pending_writes
                 .command_encoder
                 .transition_buffers(iter::once(hal::BufferBarrier {
@@ -363,6 +377,7 @@ impl<A: HalApi> Device<A> {
             adapter_id,
             queue: open.queue,
             zero_buffer,
+            zero_buffer_size,
             life_guard: LifeGuard::new("<device>"),
             command_allocator: Mutex::new(com_alloc),
             active_submission_index: 0,
@@ -388,6 +403,10 @@ impl<A: HalApi> Device<A> {
             limits: desc.limits.clone(),
             features: desc.features,
             downlevel,
+            srgb_clear_needs_render_pass,
+            skip_debug_markers: false,
+            warn_missing_dispatch: false,
+            warn_pipeline_without_writes: false,
             pending_writes,
         })
     }
@@ -449,7 +468,7 @@ impl<A: HalApi> Device<A> {
         })
     }
 
-    fn untrack<'this, 'token: 'this, G: GlobalIdentityHandlerFactory>(
+    pub(crate) fn untrack<'this, 'token: 'this, G: GlobalIdentityHandlerFactory>(
         &'this mut self,
         hub: &Hub<A, G>,
         trackers: &TrackerSet,
@@ -538,11 +557,21 @@ impl<A: HalApi> Device<A> {
                 // we are going to be copying into it, internally
                 usage |= hal::BufferUses::COPY_DST;
             }
+            if desc.usage.contains(wgt::BufferUsages::STORAGE) {
+                // storage buffers can be cleared without the `COPY_DST` usage
+                usage |= hal::BufferUses::COPY_DST;
+            }
         } else {
             // We are required to zero out (initialize) all memory.
             // This is done on demand using clear_buffer which requires write transfer usage!
             usage |= hal::BufferUses::COPY_DST;
         }
+        if desc.usage.contains(wgt::BufferUsages::INDIRECT)
+            && self.features.contains(wgt::Features::CONDITIONAL_DISPATCH)
+        {
+            // any indirect buffer may be the predicate of a conditional dispatch
+            usage |= hal::BufferUses::PREDICATE;
+        }
 
         let actual_size = if desc.size == 0 {
             wgt::COPY_BUFFER_ALIGNMENT
@@ -616,6 +645,8 @@ impl<A: HalApi> Device<A> {
                 layers: 0..desc.array_layer_count(),
             },
             life_guard: LifeGuard::new(desc.label.borrow_or_default()),
+            external: false,
+            clear_views: Vec::new(),
         }
     }
 
@@ -626,8 +657,14 @@ impl<A: HalApi> Device<A> {
         desc: &resource::TextureDescriptor,
     ) -> Result<resource::Texture<A>, resource::CreateTextureError> {
         // Enforce COPY_DST, otherwise we wouldn't be able to initialize the texture.
-        let hal_usage =
+        let mut hal_usage =
             conv::map_texture_usage(desc.usage, desc.format.into()) | hal::TextureUses::COPY_DST;
+        // Depth/stencil textures are cleared with a render pass,
+        // so they always need to be usable as an attachment.
+        let is_depth_stencil = desc.format.describe().sample_type == wgt::TextureSampleType::Depth;
+        if is_depth_stencil {
+            hal_usage |= hal::TextureUses::DEPTH_STENCIL_WRITE;
+        }
 
         let hal_desc = hal::TextureDescriptor {
             label: desc.label.borrow_option(),
@@ -687,8 +724,54 @@ impl<A: HalApi> Device<A> {
                 .map_err(DeviceError::from)?
         };
 
+        // Views for clearing with a render pass, see `clear_texture_via_render_pass`.
+        let clear_view_usage = if is_depth_stencil {
+            Some(hal::TextureUses::DEPTH_STENCIL_WRITE)
+        } else if desc.usage.contains(wgt::TextureUsages::RENDER_ATTACHMENT)
+            && desc.dimension == wgt::TextureDimension::D2
+        {
+            Some(hal::TextureUses::COLOR_TARGET)
+        } else {
+            None
+        };
+        let mut clear_views = Vec::new();
+        if let Some(usage) = clear_view_usage {
+            for mip_level in 0..desc.mip_level_count {
+                for array_layer in 0..desc.array_layer_count() {
+                    let view_desc = hal::TextureViewDescriptor {
+                        label: Some("(wgpu internal) clear texture view"),
+                        format: desc.format,
+                        dimension: wgt::TextureViewDimension::D2,
+                        usage,
+                        range: wgt::ImageSubresourceRange {
+                            aspect: wgt::TextureAspect::All,
+                            base_mip_level: mip_level,
+                            mip_level_count: NonZeroU32::new(1),
+                            base_array_layer: array_layer,
+                            array_layer_count: NonZeroU32::new(1),
+                        },
+                    };
+                    match unsafe { self.raw.create_texture_view(&raw, &view_desc) } {
+                        Ok(view) => clear_views.push(view),
+                        Err(error) => {
+                            // Nothing refers to the texture yet, so it's destroyed right away.
+                            unsafe {
+                                for view in clear_views {
+                                    self.raw.destroy_texture_view(view);
+                                }
+                                self.raw.destroy_texture(raw);
+                            }
+                            return Err(DeviceError::from(error).into());
+                        }
+                    }
+                }
+            }
+        }
+
         let mut texture = self.create_texture_from_hal(raw, self_id, desc, format_features);
         texture.hal_usage = hal_usage;
+        texture.clear_views = clear_views;
+
         Ok(texture)
     }
 
@@ -903,6 +986,13 @@ impl<A: HalApi> Device<A> {
         {
             self.require_features(wgt::Features::ADDRESS_MODE_CLAMP_TO_BORDER)?;
         }
+        if desc
+            .address_modes
+            .iter()
+            .any(|am| am == &wgt::AddressMode::MirrorClampToEdge)
+        {
+            self.require_features(wgt::Features::ADDRESS_MODE_MIRROR_CLAMP_TO_EDGE)?;
+        }
 
         let lod_clamp = if desc.lod_min_clamp > 0.0 || desc.lod_max_clamp < 32.0 {
             Some(desc.lod_min_clamp..desc.lod_max_clamp)
@@ -1259,7 +1349,7 @@ impl<A: HalApi> Device<A> {
         // If a single bind group layout violates limits, the pipeline layout is definitely
         // going to violate limits too, lets catch it now.
         count_validator
-            .validate(&self.limits)
+            .validate(&self.limits, &self.downlevel.limits)
             .map_err(binding_model::CreateBindGroupLayoutError::TooManyBindings)?;
 
         Ok(binding_model::BindGroupLayout {
@@ -1885,7 +1975,7 @@ impl<A: HalApi> Device<A> {
             count_validator.merge(&bind_group_layout.count_validator);
         }
         count_validator
-            .validate(&self.limits)
+            .validate(&self.limits, &self.downlevel.limits)
             .map_err(Error::TooManyBindings)?;
 
         let bgl_vec = desc
@@ -2007,6 +2097,15 @@ impl<A: HalApi> Device<A> {
         let shader_module = shader_module_guard
             .get(desc.stage.module)
             .map_err(|_| validation::StageError::InvalidModule)?;
+        let workgroup_size = shader_module
+            .interface
+            .as_ref()
+            .and_then(|interface| interface.compute_workgroup_size(&desc.stage.entry_point));
+        let writes_resources = shader_module
+            .interface
+            .as_ref()
+            .and_then(|interface| interface.compute_writes_resources(&desc.stage.entry_point))
+            .unwrap_or(true);
 
         {
             let flag = wgt::ShaderStages::COMPUTE;
@@ -2083,6 +2182,8 @@ impl<A: HalApi> Device<A> {
                 value: id::Valid(self_id),
                 ref_count: self.life_guard.add_ref(),
             },
+            workgroup_size,
+            writes_resources,
             life_guard: LifeGuard::new(desc.label.borrow_or_default()),
         };
         Ok(pipeline)
@@ -2280,6 +2381,9 @@ impl<A: HalApi> Device<A> {
             if let Some(e) = error {
                 return Err(pipeline::CreateRenderPipelineError::DepthStencilState(e));
             }
+            if ds.bias.clamp != 0.0 {
+                self.require_downlevel_flags(wgt::DownlevelFlags::DEPTH_BIAS_CLAMP)?;
+            }
         }
 
         if desc.layout.is_none() {
@@ -2356,6 +2460,12 @@ impl<A: HalApi> Device<A> {
                             error: validation::StageError::InvalidModule,
                         })?;
 
+                if let Some(ref interface) = shader_module.interface {
+                    if interface.fragment_uses_sample_shading(&fragment.stage.entry_point) {
+                        self.require_downlevel_flags(wgt::DownlevelFlags::MULTISAMPLED_SHADING)?;
+                    }
+                }
+
                 let provided_layouts = match desc.layout {
                     Some(pipeline_layout_id) => Some(Device::get_introspection_bind_group_layouts(
                         pipeline_layout_guard
@@ -2668,6 +2778,8 @@ pub enum DeviceError {
     Lost,
     #[error("not enough memory left")]
     OutOfMemory,
+    #[error("the resource doesn't fit into the implementation limits")]
+    ResourceCreationFailed,
 }
 
 impl From<hal::DeviceError> for DeviceError {
@@ -2675,6 +2787,7 @@ impl From<hal::DeviceError> for DeviceError {
         match error {
             hal::DeviceError::Lost => DeviceError::Lost,
             hal::DeviceError::OutOfMemory => DeviceError::OutOfMemory,
+            hal::DeviceError::ResourceCreationFailed => DeviceError::ResourceCreationFailed,
         }
     }
 }
@@ -2792,6 +2905,60 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         Ok(device.downlevel.clone())
     }
 
+    /// Makes compute passes run on this device skip their debug groups and markers.
+    ///
+    /// They are still recorded and validated, but don't reach the backend.
+    pub fn device_set_skip_debug_markers<A: HalApi>(
+        &self,
+        device_id: id::DeviceId,
+        skip: bool,
+    ) -> Result<(), InvalidDevice> {
+        let hub = A::hub(self);
+        let mut token = Token::root();
+        let (mut device_guard, _) = hub.devices.write(&mut token);
+        let device = device_guard.get_mut(device_id).map_err(|_| InvalidDevice)?;
+
+        device.skip_debug_markers = skip;
+        Ok(())
+    }
+
+    /// Makes compute passes run on this device warn when a pipeline and all
+    /// the bind groups it needs are set, but the pass ends or the pipeline is
+    /// changed without a dispatch in between.
+    ///
+    /// This catches passes that silently do nothing because a dispatch was forgotten.
+    pub fn device_set_warn_missing_dispatch<A: HalApi>(
+        &self,
+        device_id: id::DeviceId,
+        warn: bool,
+    ) -> Result<(), InvalidDevice> {
+        let hub = A::hub(self);
+        let mut token = Token::root();
+        let (mut device_guard, _) = hub.devices.write(&mut token);
+        let device = device_guard.get_mut(device_id).map_err(|_| InvalidDevice)?;
+
+        device.warn_missing_dispatch = warn;
+        Ok(())
+    }
+
+    /// Makes compute passes run on this device warn when dispatching a pipeline
+    /// that doesn't write to any resource, once per pipeline change.
+    ///
+    /// Such a dispatch can't have any effect, which usually means a binding was forgotten.
+    pub fn device_set_warn_pipeline_without_writes<A: HalApi>(
+        &self,
+        device_id: id::DeviceId,
+        warn: bool,
+    ) -> Result<(), InvalidDevice> {
+        let hub = A::hub(self);
+        let mut token = Token::root();
+        let (mut device_guard, _) = hub.devices.write(&mut token);
+        let device = device_guard.get_mut(device_id).map_err(|_| InvalidDevice)?;
+
+        device.warn_pipeline_without_writes = warn;
+        Ok(())
+    }
+
     pub fn device_create_buffer<A: HalApi>(
         &self,
         device_id: id::DeviceId,
@@ -3230,8 +3397,15 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 Err(error) => break error,
             };
 
-            let texture =
+            let mut texture =
                 device.create_texture_from_hal(hal_texture, device_id, desc, format_features);
+            // The contents of an imported texture are provided by its source,
+            // so they must not be zero-initialized on first use.
+            texture.external = true;
+            texture.initialization_status = TextureInitTracker::new_initialized(
+                desc.mip_level_count,
+                desc.size.depth_or_array_layers,
+            );
             let num_levels = texture.full_range.levels.end;
             let num_layers = texture.full_range.layers.end;
             let ref_count = texture.life_guard.add_ref();
@@ -3284,7 +3458,8 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         match texture.inner {
             resource::TextureInner::Native { ref mut raw } => {
                 let raw = raw.take().ok_or(resource::DestroyError::AlreadyDestroyed)?;
-                let temp = queue::TempResource::Texture(raw);
+                let clear_views = mem::take(&mut texture.clear_views);
+                let temp = queue::TempResource::Texture(raw, clear_views);
 
                 if device.pending_writes.dst_textures.contains(&texture_id) {
                     device.pending_writes.temp_resources.push(temp);
@@ -3708,6 +3883,33 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         device_id: id::DeviceId,
         desc: &binding_model::BindGroupDescriptor,
         id_in: Input<G, id::BindGroupId>,
+    ) -> (id::BindGroupId, Option<binding_model::CreateBindGroupError>) {
+        self.create_bind_group_impl::<A>(device_id, desc, id_in, false)
+    }
+
+    /// Creates a bind group whose bound buffer ranges and texture subresources
+    /// are marked as initialized, so using it never zero-initializes them.
+    ///
+    /// # Safety
+    ///
+    /// Every bound buffer range and texture subresource must be fully written
+    /// before the bind group is used in a submission. Otherwise shaders may
+    /// read uninitialized memory.
+    pub unsafe fn device_create_bind_group_assume_initialized<A: HalApi>(
+        &self,
+        device_id: id::DeviceId,
+        desc: &binding_model::BindGroupDescriptor,
+        id_in: Input<G, id::BindGroupId>,
+    ) -> (id::BindGroupId, Option<binding_model::CreateBindGroupError>) {
+        self.create_bind_group_impl::<A>(device_id, desc, id_in, true)
+    }
+
+    fn create_bind_group_impl<A: HalApi>(
+        &self,
+        device_id: id::DeviceId,
+        desc: &binding_model::BindGroupDescriptor,
+        id_in: Input<G, id::BindGroupId>,
+        assume_initialized: bool,
     ) -> (id::BindGroupId, Option<binding_model::CreateBindGroupError>) {
         profiling::scope!("create_bind_group", "Device");
 
@@ -3740,6 +3942,25 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                     Ok(bind_group) => bind_group,
                     Err(e) => break e,
                 };
+            if assume_initialized {
+                let (mut buffer_guard, mut token) = hub.buffers.write(&mut token);
+                let (mut texture_guard, _) = hub.textures.write(&mut token);
+                // Dropping the drains marks the ranges as initialized.
+                for action in bind_group.used_buffer_ranges.iter() {
+                    let buffer = buffer_guard.get_mut(action.id).unwrap();
+                    buffer.initialization_status.drain(action.range.clone());
+                }
+                for action in bind_group.used_texture_ranges.iter() {
+                    let texture = texture_guard.get_mut(action.id).unwrap();
+                    let mip_range = &action.range.mip_range;
+                    for mip in texture.initialization_status.mips
+                        [mip_range.start as usize..mip_range.end as usize]
+                        .iter_mut()
+                    {
+                        mip.drain(action.range.layer_range.clone());
+                    }
+                }
+            }
             let ref_count = bind_group.life_guard.add_ref();
 
             let id = fid.assign(bind_group, &mut token);
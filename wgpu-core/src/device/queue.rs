@@ -63,7 +63,8 @@ impl<A: hal::Api> StagingData<A> {
 #[derive(Debug)]
 pub enum TempResource<A: hal::Api> {
     Buffer(A::Buffer),
-    Texture(A::Texture),
+    /// A texture along with its internal clear views.
+    Texture(A::Texture, Vec<A::TextureView>),
 }
 
 /// A queue execution for a particular command encoder.
@@ -116,7 +117,10 @@ impl<A: hal::Api> PendingWrites<A> {
                 TempResource::Buffer(buffer) => unsafe {
                     device.destroy_buffer(buffer);
                 },
-                TempResource::Texture(texture) => unsafe {
+                TempResource::Texture(texture, clear_views) => unsafe {
+                    for view in clear_views {
+                        device.destroy_texture_view(view);
+                    }
                     device.destroy_texture(texture);
                 },
             }
@@ -542,6 +546,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                     crate::command::collect_zero_buffer_copies_for_clear_texture(
                         &dst.desc,
                         device.alignments.buffer_copy_pitch.get() as u32,
+                        device.zero_buffer_size,
                         destination.mip_level..(destination.mip_level + 1),
                         layer,
                         &mut zero_buffer_copy_regions,
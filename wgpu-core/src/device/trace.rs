@@ -152,10 +152,27 @@ pub enum Command {
         offset: wgt::BufferAddress,
         size: Option<wgt::BufferSize>,
     },
+    FillBuffer {
+        dst: id::BufferId,
+        offset: wgt::BufferAddress,
+        size: Option<wgt::BufferSize>,
+        value: u32,
+    },
     ClearTexture {
         dst: id::TextureId,
         subresource_range: wgt::ImageSubresourceRange,
     },
+    ClearTextureWithValue {
+        dst: id::TextureId,
+        subresource_range: wgt::ImageSubresourceRange,
+        value: wgt::Color,
+    },
+    ClearDepthTexture {
+        dst: id::TextureId,
+        subresource_range: wgt::ImageSubresourceRange,
+        depth: f32,
+        stencil: Option<u32>,
+    },
     WriteTimestamp {
         query_set_id: id::QuerySetId,
         query_index: u32,
@@ -169,6 +186,9 @@ pub enum Command {
     },
     RunComputePass {
         base: crate::command::BasePass<crate::command::ComputeCommand>,
+        max_dispatch_count: Option<u32>,
+        #[cfg_attr(feature = "replay", serde(default))]
+        batch_barriers: bool,
     },
     RunRenderPass {
         base: crate::command::BasePass<crate::command::RenderCommand>,
@@ -648,6 +648,11 @@ impl<A: HalApi, F: GlobalIdentityHandlerFactory> Hub<A, F> {
         for element in self.textures.data.write().map.drain(..) {
             if let Element::Occupied(texture, _) = element {
                 let device = &devices[texture.device_id.value];
+                for view in texture.clear_views {
+                    unsafe {
+                        device.raw.destroy_texture_view(view);
+                    }
+                }
                 if let TextureInner::Native { raw: Some(raw) } = texture.inner {
                     unsafe {
                         device.raw.destroy_texture(raw);
@@ -1040,6 +1045,25 @@ impl HalApi for hal::api::Gles {
     }
 }
 
+/// Lets unit tests run backend generic code without a GPU. There is no hub
+/// or surface for it, as no `Global` is created in unit tests.
+#[cfg(test)]
+impl HalApi for hal::api::Empty {
+    const VARIANT: Backend = Backend::Empty;
+    fn create_instance_from_hal(_name: &str, _hal_instance: Self::Instance) -> Instance {
+        unreachable!()
+    }
+    fn hub<G: GlobalIdentityHandlerFactory>(_global: &Global<G>) -> &Hub<Self, G> {
+        unreachable!()
+    }
+    fn get_surface(_surface: &Surface) -> &HalSurface<Self> {
+        unreachable!()
+    }
+    fn get_surface_mut(_surface: &mut Surface) -> &mut HalSurface<Self> {
+        unreachable!()
+    }
+}
+
 #[cfg(test)]
 fn _test_send_sync(global: &Global<IdentityManagerFactory>) {
     fn test_internal<T: Send + Sync>(_: T) {}
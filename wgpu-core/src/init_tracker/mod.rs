@@ -348,4 +348,13 @@ mod test {
         assert_eq!(tracker.uninitialized_ranges.len(), 1);
         assert_eq!(tracker.uninitialized_ranges[0], 0..10);
     }
+
+    #[test]
+    fn texture_tracker_created_initialized() {
+        let tracker = super::TextureInitTracker::new_initialized(3, 4);
+        assert_eq!(tracker.mips.len(), 3);
+        for mip_tracker in tracker.mips.iter() {
+            assert_eq!(mip_tracker.check(0..4), None);
+        }
+    }
 }
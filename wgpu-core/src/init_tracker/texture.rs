@@ -41,6 +41,16 @@ impl TextureInitTracker {
         }
     }
 
+    /// Creates a tracker for a texture whose memory is initialized already.
+    pub(crate) fn new_initialized(mip_level_count: u32, depth_or_array_layers: u32) -> Self {
+        let mut tracker = Self::new(mip_level_count, depth_or_array_layers);
+        for mip_tracker in tracker.mips.iter_mut() {
+            // Dropping the drain marks the whole range as initialized.
+            mip_tracker.drain(0..depth_or_array_layers);
+        }
+        tracker
+    }
+
     pub(crate) fn check_action(
         &self,
         action: &TextureInitTrackerAction,
@@ -64,6 +64,10 @@ fn check_limits(requested: &wgt::Limits, allowed: &wgt::Limits) -> Vec<FailedLim
     compare!(max_push_constant_size, Less);
     compare!(min_uniform_buffer_offset_alignment, Greater);
     compare!(min_storage_buffer_offset_alignment, Greater);
+    compare!(max_compute_workgroups_per_dimension, Less);
+    compare!(max_compute_workgroups_x, Less);
+    compare!(max_compute_workgroups_y, Less);
+    compare!(max_compute_workgroups_z, Less);
     failed
 }
 
@@ -317,6 +321,14 @@ impl<A: HalApi> Adapter<A> {
             return Err(RequestDeviceError::LimitsExceeded(failed));
         }
 
+        // Copies from the zero buffer start at offset 0 and use whole rows,
+        // which are aligned to `COPY_BYTES_PER_ROW_ALIGNMENT` at most.
+        if let Some(size) = desc.zero_buffer_size {
+            if size.get() % wgt::COPY_BYTES_PER_ROW_ALIGNMENT as u64 != 0 {
+                return Err(RequestDeviceError::UnalignedZeroBufferSize(size));
+            }
+        }
+
         Device::new(
             open,
             Stored {
@@ -325,6 +337,7 @@ impl<A: HalApi> Adapter<A> {
             },
             caps.alignments.clone(),
             caps.downlevel.clone(),
+            caps.srgb_clear_needs_render_pass,
             desc,
             trace_path,
         )
@@ -341,6 +354,7 @@ impl<A: HalApi> Adapter<A> {
             |err| match err {
                 hal::DeviceError::Lost => RequestDeviceError::DeviceLost,
                 hal::DeviceError::OutOfMemory => RequestDeviceError::OutOfMemory,
+                hal::DeviceError::ResourceCreationFailed => RequestDeviceError::Internal,
             },
         )?;
 
@@ -393,6 +407,8 @@ pub enum RequestDeviceError {
     OutOfMemory,
     #[error("unsupported features were requested: {0:?}")]
     UnsupportedFeature(wgt::Features),
+    #[error("zero buffer size {0} is not a multiple of `COPY_BYTES_PER_ROW_ALIGNMENT`")]
+    UnalignedZeroBufferSize(wgt::BufferSize),
 }
 
 pub enum AdapterInputs<'a, I> {
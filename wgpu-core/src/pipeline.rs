@@ -167,6 +167,10 @@ pub struct ComputePipeline<A: hal::Api> {
     pub(crate) raw: A::ComputePipeline,
     pub(crate) layout_id: Stored<PipelineLayoutId>,
     pub(crate) device_id: Stored<DeviceId>,
+    /// Workgroup size of the entry point, known if the shader was validated.
+    pub(crate) workgroup_size: Option<[u32; 3]>,
+    /// Whether the entry point writes to any resource. Assumed if the shader wasn't validated.
+    pub(crate) writes_resources: bool,
     pub(crate) life_guard: LifeGuard,
 }
 
@@ -158,6 +158,8 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                         levels: 0..1,
                     },
                     life_guard: LifeGuard::new("<Surface>"),
+                    external: false,
+                    clear_views: Vec::new(),
                 };
 
                 let ref_count = texture.life_guard.add_ref();
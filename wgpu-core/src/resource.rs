@@ -188,6 +188,19 @@ pub struct Texture<A: hal::Api> {
     pub(crate) initialization_status: TextureInitTracker,
     pub(crate) full_range: TextureSelector,
     pub(crate) life_guard: LifeGuard,
+    /// Whether the texture was imported from outside of wgpu (e.g. from an EGLImage).
+    /// Its contents are defined externally, so its memory initialization isn't tracked.
+    pub(crate) external: bool,
+    /// Views of every mip level and array layer, used for clearing
+    /// with a render pass. Empty for textures that can't be render targets.
+    pub(crate) clear_views: Vec<A::TextureView>,
+}
+
+impl<A: hal::Api> Texture<A> {
+    pub(crate) fn clear_view(&self, mip_level: u32, array_layer: u32) -> Option<&A::TextureView> {
+        let index = mip_level * self.full_range.layers.end + array_layer;
+        self.clear_views.get(index as usize)
+    }
 }
 
 impl<G: GlobalIdentityHandlerFactory> Global<G> {
@@ -430,7 +443,8 @@ pub enum CreateSamplerError {
     InvalidClamp(u8),
     #[error("cannot create any more samplers")]
     TooManyObjects,
-    /// AddressMode::ClampToBorder requires feature ADDRESS_MODE_CLAMP_TO_BORDER.
+    /// AddressMode::ClampToBorder requires feature ADDRESS_MODE_CLAMP_TO_BORDER,
+    /// AddressMode::MirrorClampToEdge requires feature ADDRESS_MODE_MIRROR_CLAMP_TO_EDGE.
     #[error(transparent)]
     MissingFeatures(#[from] MissingFeatures),
 }
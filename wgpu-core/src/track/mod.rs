@@ -280,7 +280,7 @@ impl<S: ResourceState> ResourceTracker<S> {
         self.map.is_empty()
     }
 
-    /// Clear the tracked contents.
+    /// Clear the tracked contents, keeping the allocated capacity.
     pub fn clear(&mut self) {
         self.map.clear();
     }
@@ -635,6 +635,10 @@ impl StatefulTrackerSubset {
     }
 
     /// Clear all the trackers.
+    ///
+    /// The allocated capacity is kept, so a subset that is cleared after
+    /// every dispatch only grows up to the largest set of resources a single
+    /// dispatch uses.
     pub fn clear(&mut self) {
         self.buffers.clear();
         self.textures.clear();
@@ -647,3 +651,38 @@ impl StatefulTrackerSubset {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{id::BufferId, LifeGuard};
+
+    #[test]
+    fn subset_reuses_capacity_across_merges() {
+        let backend = wgt::Backend::Empty;
+        let life_guard = LifeGuard::new("");
+        let mut bind_group_set = TrackerSet::new(backend);
+        for index in 0..64 {
+            let id = Valid(BufferId::zip(index, 1, backend));
+            bind_group_set
+                .buffers
+                .init(
+                    id,
+                    life_guard.add_ref(),
+                    BufferState::new(hal::BufferUses::STORAGE_READ),
+                )
+                .unwrap();
+        }
+
+        let mut subset = StatefulTrackerSubset::new(backend);
+        subset.merge_extend(&bind_group_set).unwrap();
+        let capacity = subset.buffers.map.capacity();
+
+        for _ in 0..4 {
+            subset.clear();
+            assert!(subset.buffers.is_empty());
+            subset.merge_extend(&bind_group_set).unwrap();
+            assert_eq!(subset.buffers.map.capacity(), capacity);
+        }
+    }
+}
@@ -3,6 +3,7 @@ use crate::id::{TextureId, Valid};
 use hal::TextureUses;
 
 use arrayvec::ArrayVec;
+use smallvec::SmallVec;
 
 use std::{iter, ops::Range};
 
@@ -142,7 +143,8 @@ impl ResourceState for TextureState {
         other: &Self,
         mut output: Option<&mut Vec<PendingTransition<Self>>>,
     ) -> Result<(), PendingTransition<Self>> {
-        let mut temp = Vec::new();
+        // Merging usually produces very few ranges per mip, so keep them off the heap.
+        let mut temp = SmallVec::<[_; 4]>::new();
         if self.full {
             assert!(self.mips.len() >= other.mips.len());
         } else {
@@ -949,6 +949,38 @@ impl Interface {
         }
     }
 
+    /// Returns the workgroup size of the compute entry point with the given name.
+    pub(crate) fn compute_workgroup_size(&self, entry_point_name: &str) -> Option<[u32; 3]> {
+        self.entry_points
+            .get(&(naga::ShaderStage::Compute, entry_point_name.to_string()))
+            .map(|entry_point| entry_point.workgroup_size)
+    }
+
+    /// Returns whether the compute entry point with the given name writes to any resource.
+    pub(crate) fn compute_writes_resources(&self, entry_point_name: &str) -> Option<bool> {
+        self.entry_points
+            .get(&(naga::ShaderStage::Compute, entry_point_name.to_string()))
+            .map(|entry_point| {
+                entry_point
+                    .resources
+                    .iter()
+                    .any(|&(_, usage)| usage.contains(GlobalUse::WRITE))
+            })
+    }
+
+    /// Returns `true` if the fragment entry point has to run once per sample,
+    /// because it reads `sample_index` or has an input with `sample` sampling.
+    pub fn fragment_uses_sample_shading(&self, entry_point_name: &str) -> bool {
+        let pair = (naga::ShaderStage::Fragment, entry_point_name.to_string());
+        match self.entry_points.get(&pair) {
+            Some(entry_point) => entry_point.inputs.iter().any(|input| match *input {
+                Varying::Local { ref iv, .. } => iv.sampling == Some(naga::Sampling::Sample),
+                Varying::BuiltIn(built_in) => built_in == naga::BuiltIn::SampleIndex,
+            }),
+            None => false,
+        }
+    }
+
     pub fn check_stage(
         &self,
         given_layouts: Option<&[&BindEntryMap]>,
@@ -1135,3 +1167,53 @@ impl Interface {
         Ok(outputs)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Interface;
+
+    fn interface(source: &str) -> Interface {
+        let module = naga::front::wgsl::parse_str(source).unwrap();
+        let info = naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::all(),
+        )
+        .validate(&module)
+        .unwrap();
+        Interface::new(
+            &module,
+            &info,
+            wgt::Features::empty(),
+            wgt::Limits::default(),
+        )
+    }
+
+    #[test]
+    fn fragment_sample_shading_detection() {
+        let interface = interface(
+            "
+            [[stage(fragment)]]
+            fn by_pixel([[location(0)]] color: vec4<f32>) -> [[location(0)]] vec4<f32> {
+                return color;
+            }
+
+            [[stage(fragment)]]
+            fn by_sample_index([[builtin(sample_index)]] index: u32) -> [[location(0)]] vec4<f32> {
+                return vec4<f32>(f32(index));
+            }
+
+            [[stage(fragment)]]
+            fn by_sample_input(
+                [[location(0), interpolate(perspective, sample)]] color: vec4<f32>,
+            ) -> [[location(0)]] vec4<f32> {
+                return color;
+            }
+            ",
+        );
+
+        assert!(!interface.fragment_uses_sample_shading("by_pixel"));
+        assert!(interface.fragment_uses_sample_shading("by_sample_index"));
+        assert!(interface.fragment_uses_sample_shading("by_sample_input"));
+        assert!(!interface.fragment_uses_sample_shading("missing"));
+    }
+}
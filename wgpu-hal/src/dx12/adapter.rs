@@ -178,6 +178,7 @@ impl super::Adapter {
             //| wgt::Features::UNSIZED_BINDING_ARRAY
             | wgt::Features::MULTI_DRAW_INDIRECT
             | wgt::Features::MULTI_DRAW_INDIRECT_COUNT
+            | wgt::Features::MULTI_DISPATCH_INDIRECT_COUNT
             | wgt::Features::ADDRESS_MODE_CLAMP_TO_BORDER
             | wgt::Features::POLYGON_MODE_LINE
             | wgt::Features::POLYGON_MODE_POINT
@@ -251,6 +252,12 @@ impl super::Adapter {
                     max_compute_workgroup_size_z: d3d12::D3D12_CS_THREAD_GROUP_MAX_Z,
                     max_compute_workgroups_per_dimension:
                         d3d12::D3D12_CS_DISPATCH_MAX_THREAD_GROUPS_PER_DIMENSION,
+                    max_compute_workgroups_x:
+                        d3d12::D3D12_CS_DISPATCH_MAX_THREAD_GROUPS_PER_DIMENSION,
+                    max_compute_workgroups_y:
+                        d3d12::D3D12_CS_DISPATCH_MAX_THREAD_GROUPS_PER_DIMENSION,
+                    max_compute_workgroups_z:
+                        d3d12::D3D12_CS_DISPATCH_MAX_THREAD_GROUPS_PER_DIMENSION,
                     // TODO?
                 },
                 alignments: crate::Alignments {
@@ -264,6 +271,7 @@ impl super::Adapter {
                     .unwrap(),
                 },
                 downlevel: wgt::DownlevelCapabilities::default(),
+                srgb_clear_needs_render_pass: false,
             },
         })
     }
@@ -1,6 +1,10 @@
 use super::{conv, HResult as _};
 use std::{mem, ops::Range, ptr};
-use winapi::um::d3d12;
+use winapi::{
+    shared::{dxgiformat, dxgitype},
+    um::d3d12,
+    Interface,
+};
 
 fn make_box(origin: &wgt::Origin3d, size: &crate::CopyExtent) -> d3d12::D3D12_BOX {
     d3d12::D3D12_BOX {
@@ -220,6 +224,9 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
             self.free_lists.push(cmd_buf.raw);
         }
         self.allocator.reset();
+        for pattern_buffer in self.fill_pattern_buffers.drain(..) {
+            pattern_buffer.destroy();
+        }
     }
 
     unsafe fn transition_buffers<'a, T>(&mut self, barriers: T)
@@ -367,6 +374,84 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         }
     }
 
+    unsafe fn fill_buffer(
+        &mut self,
+        buffer: &super::Buffer,
+        range: crate::MemoryRange,
+        value: u32,
+    ) {
+        // D3D12 can't fill a buffer with a value directly,
+        // so copy from an upload buffer holding the repeated pattern.
+        let pattern_size = (range.end - range.start).min(super::ZERO_BUFFER_SIZE);
+        let raw_desc = d3d12::D3D12_RESOURCE_DESC {
+            Dimension: d3d12::D3D12_RESOURCE_DIMENSION_BUFFER,
+            Alignment: 0,
+            Width: pattern_size,
+            Height: 1,
+            DepthOrArraySize: 1,
+            MipLevels: 1,
+            Format: dxgiformat::DXGI_FORMAT_UNKNOWN,
+            SampleDesc: dxgitype::DXGI_SAMPLE_DESC {
+                Count: 1,
+                Quality: 0,
+            },
+            Layout: d3d12::D3D12_TEXTURE_LAYOUT_ROW_MAJOR,
+            Flags: d3d12::D3D12_RESOURCE_FLAG_NONE,
+        };
+        let heap_properties = d3d12::D3D12_HEAP_PROPERTIES {
+            Type: d3d12::D3D12_HEAP_TYPE_UPLOAD,
+            CPUPageProperty: d3d12::D3D12_CPU_PAGE_PROPERTY_UNKNOWN,
+            MemoryPoolPreference: d3d12::D3D12_MEMORY_POOL_UNKNOWN,
+            CreationNodeMask: 0,
+            VisibleNodeMask: 0,
+        };
+
+        let mut pattern_buffer = native::Resource::null();
+        let hr = self.device.CreateCommittedResource(
+            &heap_properties,
+            d3d12::D3D12_HEAP_FLAG_NONE,
+            &raw_desc,
+            d3d12::D3D12_RESOURCE_STATE_GENERIC_READ,
+            ptr::null(),
+            &d3d12::ID3D12Resource::uuidof(),
+            pattern_buffer.mut_void(),
+        );
+        if hr
+            .into_device_result("Fill pattern buffer creation")
+            .is_err()
+        {
+            return;
+        }
+
+        let mut mapping = ptr::null_mut();
+        let hr = (*pattern_buffer).Map(0, ptr::null(), &mut mapping);
+        if hr.into_device_result("Fill pattern buffer map").is_err() {
+            pattern_buffer.destroy();
+            return;
+        }
+        let pattern = std::slice::from_raw_parts_mut(mapping as *mut u8, pattern_size as usize);
+        for chunk in pattern.chunks_exact_mut(4) {
+            chunk.copy_from_slice(&value.to_ne_bytes());
+        }
+        (*pattern_buffer).Unmap(0, ptr::null());
+
+        let list = self.list.unwrap();
+        let mut offset = range.start;
+        while offset < range.end {
+            let size = pattern_size.min(range.end - offset);
+            list.CopyBufferRegion(
+                buffer.resource.as_mut_ptr(),
+                offset,
+                pattern_buffer.as_mut_ptr(),
+                0,
+                size,
+            );
+            offset += size;
+        }
+        // Kept alive until the command buffers are reset.
+        self.fill_pattern_buffers.push(pattern_buffer);
+    }
+
     unsafe fn copy_buffer_to_buffer<T>(
         &mut self,
         src: &super::Buffer,
@@ -762,13 +847,15 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
     ) {
     }
 
-    unsafe fn insert_debug_marker(&mut self, label: &str) {
+    //TODO: pass the color through, which needs the PIX event encoding
+    // instead of a plain unicode string.
+    unsafe fn insert_debug_marker(&mut self, label: &str, _color: u32) {
         let (wide_label, size) = self.temp.prepare_marker(label);
         self.list
             .unwrap()
             .SetMarker(0, wide_label.as_ptr() as *const _, size);
     }
-    unsafe fn begin_debug_marker(&mut self, group_label: &str) {
+    unsafe fn begin_debug_marker(&mut self, group_label: &str, _color: u32) {
         let (wide_label, size) = self.temp.prepare_marker(group_label);
         self.list
             .unwrap()
@@ -990,4 +1077,34 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
             0,
         );
     }
+    unsafe fn dispatch_indirect_count(
+        &mut self,
+        buffer: &super::Buffer,
+        offset: wgt::BufferAddress,
+        count_buffer: &super::Buffer,
+        count_offset: wgt::BufferAddress,
+        max_count: u32,
+    ) {
+        self.prepare_dispatch([0; 3]);
+        //TODO: update special constants indirectly
+        self.list.unwrap().ExecuteIndirect(
+            self.shared.cmd_signatures.dispatch.as_mut_ptr(),
+            max_count,
+            buffer.resource.as_mut_ptr(),
+            offset,
+            count_buffer.resource.as_mut_ptr(),
+            count_offset,
+        );
+    }
+    unsafe fn dispatch_conditional(
+        &mut self,
+        _predicate: &super::Buffer,
+        _predicate_offset: wgt::BufferAddress,
+        _count: [u32; 3],
+    ) {
+        //TODO: `SetPredication` could implement this, but it reads a 64-bit
+        // predicate, so the `u32` predicates of `CONDITIONAL_DISPATCH` would
+        // have to be widened first.
+        unreachable!()
+    }
 }
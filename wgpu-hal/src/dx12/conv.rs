@@ -242,7 +242,7 @@ pub fn map_address_mode(mode: wgt::AddressMode) -> d3d12::D3D12_TEXTURE_ADDRESS_
         Am::MirrorRepeat => d3d12::D3D12_TEXTURE_ADDRESS_MODE_MIRROR,
         Am::ClampToEdge => d3d12::D3D12_TEXTURE_ADDRESS_MODE_CLAMP,
         Am::ClampToBorder => d3d12::D3D12_TEXTURE_ADDRESS_MODE_BORDER,
-        //Am::MirrorClamp => d3d12::D3D12_TEXTURE_ADDRESS_MODE_MIRROR_ONCE,
+        Am::MirrorClampToEdge => d3d12::D3D12_TEXTURE_ADDRESS_MODE_MIRROR_ONCE,
     }
 }
 
@@ -331,7 +331,7 @@ pub fn map_buffer_usage_to_state(usage: crate::BufferUses) -> d3d12::D3D12_RESOU
         state |= d3d12::D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE
             | d3d12::D3D12_RESOURCE_STATE_NON_PIXEL_SHADER_RESOURCE;
     }
-    if usage.intersects(Bu::INDIRECT) {
+    if usage.intersects(Bu::INDIRECT | Bu::PREDICATE) {
         state |= d3d12::D3D12_RESOURCE_STATE_INDIRECT_ARGUMENT;
     }
     state
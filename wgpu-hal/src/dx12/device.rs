@@ -656,6 +656,7 @@ impl crate::Device<super::Api> for super::Device {
             free_lists: Vec::new(),
             pass: super::PassState::new(),
             temp: super::Temp::default(),
+            fill_pattern_buffers: Vec::new(),
         })
     }
     unsafe fn destroy_command_encoder(&self, encoder: super::CommandEncoder) {
@@ -666,6 +667,9 @@ impl crate::Device<super::Api> for super::Device {
         for list in encoder.free_lists {
             list.destroy();
         }
+        for pattern_buffer in encoder.fill_pattern_buffers {
+            pattern_buffer.destroy();
+        }
         encoder.allocator.destroy();
     }
 
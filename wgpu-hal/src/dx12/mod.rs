@@ -353,6 +353,8 @@ pub struct CommandEncoder {
     free_lists: Vec<native::GraphicsCommandList>,
     pass: PassState,
     temp: Temp,
+    /// Upload buffers used by `fill_buffer`, destroyed on `reset_all`.
+    fill_pattern_buffers: Vec<native::Resource>,
 }
 
 unsafe impl Send for CommandEncoder {}
@@ -255,6 +255,8 @@ impl crate::CommandEncoder<Api> for Encoder {
 
     unsafe fn clear_buffer(&mut self, buffer: &Resource, range: crate::MemoryRange) {}
 
+    unsafe fn fill_buffer(&mut self, buffer: &Resource, range: crate::MemoryRange, value: u32) {}
+
     unsafe fn copy_buffer_to_buffer<T>(&mut self, src: &Resource, dst: &Resource, regions: T) {}
 
     unsafe fn copy_texture_to_texture<T>(
@@ -313,8 +315,8 @@ impl crate::CommandEncoder<Api> for Encoder {
     ) {
     }
 
-    unsafe fn insert_debug_marker(&mut self, label: &str) {}
-    unsafe fn begin_debug_marker(&mut self, group_label: &str) {}
+    unsafe fn insert_debug_marker(&mut self, label: &str, color: u32) {}
+    unsafe fn begin_debug_marker(&mut self, group_label: &str, color: u32) {}
     unsafe fn end_debug_marker(&mut self) {}
 
     unsafe fn set_render_pipeline(&mut self, pipeline: &Resource) {}
@@ -391,4 +393,20 @@ impl crate::CommandEncoder<Api> for Encoder {
 
     unsafe fn dispatch(&mut self, count: [u32; 3]) {}
     unsafe fn dispatch_indirect(&mut self, buffer: &Resource, offset: wgt::BufferAddress) {}
+    unsafe fn dispatch_indirect_count(
+        &mut self,
+        buffer: &Resource,
+        offset: wgt::BufferAddress,
+        count_buffer: &Resource,
+        count_offset: wgt::BufferAddress,
+        max_count: u32,
+    ) {
+    }
+    unsafe fn dispatch_conditional(
+        &mut self,
+        predicate: &Resource,
+        predicate_offset: wgt::BufferAddress,
+        count: [u32; 3],
+    ) {
+    }
 }
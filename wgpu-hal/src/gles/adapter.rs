@@ -6,7 +6,40 @@ use std::sync::Arc;
 const GL_UNMASKED_VENDOR_WEBGL: u32 = 0x9245;
 const GL_UNMASKED_RENDERER_WEBGL: u32 = 0x9246;
 
+/// The smallest `GL_MAX_SHADER_STORAGE_BLOCK_SIZE` allowed by GLES 3.1.
+const MIN_MAX_SHADER_STORAGE_BLOCK_SIZE: u32 = 1 << 27;
+
+/// How storage buffers can be accessed from the vertex stage.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct VertexStorageSupport {
+    vertex_storage_readable: bool,
+    vertex_storage_writable: bool,
+}
+
 impl super::Adapter {
+    /// Derives the vertex stage storage buffer support from the GL limits.
+    ///
+    /// WORKAROUND: In order to work around an issue with GL on RPI4 and similar, we ignore a
+    /// zero vertex ssbo count if there are vertex sstos. (more info:
+    /// https://github.com/gfx-rs/wgpu/pull/1607#issuecomment-874938961) The hardware does not
+    /// want us to write to these SSBOs, but GLES cannot express that. We detect this case and
+    /// only allow reading from them.
+    fn vertex_storage_support(
+        max_storage_block_size: u32,
+        vertex_shader_storage_blocks: u32,
+        vertex_shader_storage_textures: u32,
+    ) -> VertexStorageSupport {
+        let vertex_ssbo_false_zero =
+            vertex_shader_storage_blocks == 0 && vertex_shader_storage_textures != 0;
+        VertexStorageSupport {
+            vertex_storage_readable: max_storage_block_size != 0
+                && (vertex_shader_storage_blocks != 0 || vertex_ssbo_false_zero),
+            vertex_storage_writable: max_storage_block_size != 0
+                && vertex_shader_storage_blocks != 0
+                && vertex_shader_storage_textures != 0,
+        }
+    }
+
     /// According to the OpenGL specification, the version information is
     /// expected to follow the following syntax:
     ///
@@ -60,11 +93,13 @@ impl super::Adapter {
             None => (src, String::new()),
         };
 
-        // TODO: make this even more lenient so that we can also accept
+        // Anything after the minor version is ignored, so that we also accept
         // `<major> "." <minor> [<???>]`
         let mut it = version.split('.');
         let major = it.next().and_then(|s| s.parse().ok());
         let minor = it.next().and_then(|s| {
+            let digits_end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+            let s = &s[..digits_end];
             let trimmed = if s.starts_with('0') {
                 "0"
             } else {
@@ -118,19 +153,36 @@ impl super::Adapter {
             "mali",
             "intel",
             "v3d",
+            "apple m", // all apple silicon is integrated, e.g. "apple m1" through ANGLE
+        ];
+        let strings_that_imply_cpu = [
+            "mesa offscreen",
+            "swiftshader",
+            "llvmpipe",
+            "softpipe",
+            "microsoft basic render driver", // WARP, e.g. through ANGLE
+            "d3d12 warp",
+            "d3d11 warp",
         ];
-        let strings_that_imply_cpu = ["mesa offscreen", "swiftshader", "llvmpipe"];
+        let strings_that_imply_virtual = ["virgl", "virtio"];
 
+        // software and virtual renderers often name the host GPU, so check them first
         //TODO: handle Intel Iris XE as discreet
-        let inferred_device_type = if vendor.contains("qualcomm")
+        let inferred_device_type = if strings_that_imply_cpu.iter().any(|&s| renderer.contains(s)) {
+            wgt::DeviceType::Cpu
+        } else if strings_that_imply_virtual
+            .iter()
+            .any(|&s| renderer.contains(s))
+        {
+            wgt::DeviceType::VirtualGpu
+        } else if vendor.contains("qualcomm")
             || vendor.contains("intel")
+            || vendor.contains("apple")
             || strings_that_imply_integrated
                 .iter()
                 .any(|&s| renderer.contains(s))
         {
             wgt::DeviceType::IntegratedGpu
-        } else if strings_that_imply_cpu.iter().any(|&s| renderer.contains(s)) {
-            wgt::DeviceType::Cpu
         } else {
             wgt::DeviceType::DiscreteGpu
         };
@@ -150,6 +202,14 @@ impl super::Adapter {
             0x8086
         } else if vendor.contains("broadcom") {
             0x14e4
+        } else if vendor.contains("apple") {
+            0x106B
+        } else if vendor.contains("vivante") || vendor.contains("verisilicon") {
+            // Vivante has no PCI vendor id, this is the one it registered with Khronos
+            0x10001
+        } else if renderer.contains("powervr") {
+            // some PowerVR drivers report a vendor string without "imgtec"
+            0x1010
         } else {
             0
         };
@@ -157,7 +217,7 @@ impl super::Adapter {
         wgt::AdapterInfo {
             name: renderer_orig,
             vendor: vendor_id,
-            device: 0,
+            device: super::device_ids::lookup(vendor_id, &renderer),
             device_type: inferred_device_type,
             backend: wgt::Backend::Gl,
         }
@@ -175,8 +235,18 @@ impl super::Adapter {
             (glow::VENDOR, glow::RENDERER)
         };
         let (vendor, renderer) = {
-            let vendor = gl.get_parameter_string(vendor_const);
-            let renderer = gl.get_parameter_string(renderer_const);
+            let mut vendor = gl.get_parameter_string(vendor_const);
+            let mut renderer = gl.get_parameter_string(renderer_const);
+            // Some browsers advertise the extension, but return empty strings
+            // for the unmasked values for privacy reasons.
+            if vendor_const != glow::VENDOR && vendor.is_empty() {
+                log::debug!("Unmasked vendor is empty, falling back to VENDOR");
+                vendor = gl.get_parameter_string(glow::VENDOR);
+            }
+            if renderer_const != glow::RENDERER && renderer.is_empty() {
+                log::debug!("Unmasked renderer is empty, falling back to RENDERER");
+                renderer = gl.get_parameter_string(glow::RENDERER);
+            }
 
             (vendor, renderer)
         };
@@ -186,6 +256,20 @@ impl super::Adapter {
         log::info!("Renderer: {}", renderer);
         log::info!("Version: {}", version);
 
+        // `CONTEXT_PROFILE_MASK` only exists on desktop GL 3.2+, querying it
+        // on an ES or WebGL context would raise `INVALID_ENUM`.
+        if !version.contains(" ES ") && !version.starts_with("WebGL ") {
+            let profile_mask = gl.get_parameter_i32(glow::CONTEXT_PROFILE_MASK) as u32;
+            let profile = if profile_mask & glow::CONTEXT_CORE_PROFILE_BIT != 0 {
+                "core"
+            } else if profile_mask & glow::CONTEXT_COMPATIBILITY_PROFILE_BIT != 0 {
+                "compatibility"
+            } else {
+                "unknown"
+            };
+            log::info!("Profile: {}", profile);
+        }
+
         log::debug!("Extensions: {:#?}", extensions);
 
         let ver = Self::parse_version(&version).ok()?;
@@ -219,20 +303,23 @@ impl super::Adapter {
         } else {
             0
         };
+        let compute_shader_storage_textures = if supports_storage {
+            gl.get_parameter_i32(glow::MAX_COMPUTE_IMAGE_UNIFORMS) as u32
+        } else {
+            0
+        };
         let max_storage_block_size = if supports_storage {
             gl.get_parameter_i32(glow::MAX_SHADER_STORAGE_BLOCK_SIZE) as u32
         } else {
             0
         };
 
-        // WORKAROUND: In order to work around an issue with GL on RPI4 and similar, we ignore a
-        // zero vertex ssbo count if there are vertex sstos. (more info:
-        // https://github.com/gfx-rs/wgpu/pull/1607#issuecomment-874938961) The hardware does not
-        // want us to write to these SSBOs, but GLES cannot express that. We detect this case and
-        // disable writing to SSBOs.
-        let vertex_ssbo_false_zero =
-            vertex_shader_storage_blocks == 0 && vertex_shader_storage_textures != 0;
-        if vertex_ssbo_false_zero {
+        let vertex_storage_support = Self::vertex_storage_support(
+            max_storage_block_size,
+            vertex_shader_storage_blocks,
+            vertex_shader_storage_textures,
+        );
+        if vertex_shader_storage_blocks == 0 && vertex_shader_storage_textures != 0 {
             // We only care about fragment here as the 0 is a lie.
             log::warn!("Max vertex shader SSBO == 0 and SSTO != 0. Interpreting as false zero.");
         }
@@ -246,7 +333,8 @@ impl super::Adapter {
             fragment_shader_storage_textures
         } else {
             vertex_shader_storage_textures.min(fragment_shader_storage_textures)
-        };
+        }
+        .min(compute_shader_storage_textures);
 
         let mut downlevel_flags = wgt::DownlevelFlags::empty()
             | wgt::DownlevelFlags::DEVICE_LOCAL_IMAGE_COPIES
@@ -262,29 +350,64 @@ impl super::Adapter {
         //TODO: we can actually support positive `base_vertex` in the same way
         // as we emulate the `start_instance`. But we can't deal with negatives...
         downlevel_flags.set(wgt::DownlevelFlags::BASE_VERTEX, ver >= (3, 2));
+        // Per draw buffer blend state (`glEnablei`, `glBlendFunci` and friends).
+        // glow only loads the unsuffixed entry points, which drivers exposing
+        // the extension on older versions are expected to provide as well.
+        let supports_indexed_draw_buffers = cfg!(not(target_arch = "wasm32"))
+            && (ver >= (3, 2) || extensions.contains("GL_EXT_draw_buffers_indexed"));
         downlevel_flags.set(
             wgt::DownlevelFlags::INDEPENDENT_BLENDING,
-            ver >= (3, 2) || extensions.contains("GL_EXT_draw_buffers_indexed"),
+            supports_indexed_draw_buffers,
         );
         downlevel_flags.set(
             wgt::DownlevelFlags::VERTEX_STORAGE,
-            max_storage_block_size != 0
-                && (vertex_shader_storage_blocks != 0 || vertex_ssbo_false_zero),
+            vertex_storage_support.vertex_storage_readable,
         );
         downlevel_flags.set(wgt::DownlevelFlags::FRAGMENT_STORAGE, supports_storage);
+        let supports_anisotropy = extensions.contains("GL_EXT_texture_filter_anisotropic")
+            || extensions.contains("EXT_texture_filter_anisotropic");
+        // The limit is a float, and some drivers report fractional values (e.g. 15.99).
+        let max_texture_max_anisotropy = if supports_anisotropy {
+            gl.get_parameter_f32(glow::MAX_TEXTURE_MAX_ANISOTROPY_EXT)
+        } else {
+            1.0
+        };
+        downlevel_flags.set(
+            wgt::DownlevelFlags::ANISOTROPIC_FILTERING,
+            max_texture_max_anisotropy > 1.0,
+        );
+        let max_label_length = if gl.supports_debug() {
+            gl.get_parameter_i32(glow::MAX_LABEL_LENGTH) as usize
+        } else {
+            0
+        };
+        downlevel_flags.set(
+            wgt::DownlevelFlags::MULTISAMPLED_SHADING,
+            ver >= (3, 2) || extensions.contains("GL_OES_sample_shading"),
+        );
 
         let mut features = wgt::Features::empty()
             | wgt::Features::TEXTURE_COMPRESSION_ETC2
             | wgt::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES
-            | wgt::Features::CLEAR_COMMANDS;
+            | wgt::Features::CLEAR_COMMANDS
+            | wgt::Features::PUSH_CONSTANTS;
         features.set(
             wgt::Features::DEPTH_CLAMPING,
             extensions.contains("GL_EXT_depth_clamp"),
         );
+        features.set(
+            wgt::Features::CONSERVATIVE_RASTERIZATION,
+            extensions.contains("GL_NV_conservative_raster")
+                || extensions.contains("GL_INTEL_conservative_rasterization"),
+        );
+        features.set(
+            wgt::Features::ADDRESS_MODE_MIRROR_CLAMP_TO_EDGE,
+            extensions.contains("GL_EXT_texture_mirror_clamp_to_edge")
+                || extensions.contains("GL_ARB_texture_mirror_clamp_to_edge"),
+        );
         features.set(
             wgt::Features::VERTEX_WRITABLE_STORAGE,
-            downlevel_flags.contains(wgt::DownlevelFlags::VERTEX_STORAGE)
-                && vertex_shader_storage_textures != 0,
+            vertex_storage_support.vertex_storage_writable,
         );
 
         let mut private_caps = super::PrivateCapabilities::empty();
@@ -311,7 +434,71 @@ impl super::Adapter {
         );
         private_caps.set(
             super::PrivateCapabilities::CAN_DISABLE_DRAW_BUFFER,
-            cfg!(not(target_arch = "wasm32")),
+            supports_indexed_draw_buffers,
+        );
+        private_caps.set(
+            super::PrivateCapabilities::CONSERVATIVE_RASTER_INTEL,
+            !extensions.contains("GL_NV_conservative_raster")
+                && extensions.contains("GL_INTEL_conservative_rasterization"),
+        );
+        private_caps.set(
+            super::PrivateCapabilities::POLYGON_OFFSET_CLAMP,
+            extensions.contains("GL_EXT_polygon_offset_clamp")
+                && context.polygon_offset_clamp().is_some(),
+        );
+        downlevel_flags.set(
+            wgt::DownlevelFlags::DEPTH_BIAS_CLAMP,
+            private_caps.contains(super::PrivateCapabilities::POLYGON_OFFSET_CLAMP),
+        );
+        private_caps.set(
+            super::PrivateCapabilities::DEPTH32F_ATTACHMENT,
+            ver >= (3, 0),
+        );
+        // ETC2/EAC formats are core in ES 3.0, the other compressed formats
+        // are only sampleable with their extension present.
+        private_caps.set(
+            super::PrivateCapabilities::TEXTURE_COMPRESSION_S3TC,
+            extensions.contains("GL_EXT_texture_compression_s3tc")
+                || extensions.contains("WEBGL_compressed_texture_s3tc"),
+        );
+        private_caps.set(
+            super::PrivateCapabilities::TEXTURE_COMPRESSION_RGTC,
+            extensions.contains("GL_EXT_texture_compression_rgtc")
+                || extensions.contains("EXT_texture_compression_rgtc"),
+        );
+        private_caps.set(
+            super::PrivateCapabilities::TEXTURE_COMPRESSION_BPTC,
+            extensions.contains("GL_EXT_texture_compression_bptc")
+                || extensions.contains("EXT_texture_compression_bptc"),
+        );
+        private_caps.set(
+            super::PrivateCapabilities::TEXTURE_COMPRESSION_ASTC_LDR,
+            ver >= (3, 2)
+                || extensions.contains("GL_KHR_texture_compression_astc_ldr")
+                || extensions.contains("WEBGL_compressed_texture_astc"),
+        );
+        private_caps.set(
+            super::PrivateCapabilities::TIMESTAMP_QUERY,
+            (extensions.contains("GL_EXT_disjoint_timer_query")
+                || extensions.contains("GL_ARB_timer_query"))
+                && context.query_counter().is_some()
+                && context.get_query_object_u64().is_some(),
+        );
+        features.set(
+            wgt::Features::TIMESTAMP_QUERY,
+            private_caps.contains(super::PrivateCapabilities::TIMESTAMP_QUERY),
+        );
+        // Without clip control, the [0, 1] depth range is emulated by
+        // `glsl::WriterFlags::ADJUST_COORDINATE_SPACE`, losing precision.
+        private_caps.set(
+            super::PrivateCapabilities::CLIP_CONTROL,
+            (extensions.contains("GL_EXT_clip_control")
+                || extensions.contains("GL_ARB_clip_control"))
+                && context.clip_control().is_some(),
+        );
+        downlevel_flags.set(
+            wgt::DownlevelFlags::NATIVE_DEPTH_RANGE,
+            private_caps.contains(super::PrivateCapabilities::CLIP_CONTROL),
         );
 
         let max_texture_size = gl.get_parameter_i32(glow::MAX_TEXTURE_SIZE) as u32;
@@ -324,17 +511,33 @@ impl super::Adapter {
         } else {
             256
         };
-        let max_uniform_buffers_per_shader_stage =
+        let mut max_uniform_buffers_per_shader_stage =
             gl.get_parameter_i32(glow::MAX_VERTEX_UNIFORM_BLOCKS)
                 .min(gl.get_parameter_i32(glow::MAX_FRAGMENT_UNIFORM_BLOCKS)) as u32;
+        if downlevel_flags.contains(wgt::DownlevelFlags::COMPUTE_SHADERS) {
+            max_uniform_buffers_per_shader_stage = max_uniform_buffers_per_shader_stage
+                .min(gl.get_parameter_i32(glow::MAX_COMPUTE_UNIFORM_BLOCKS) as u32);
+        }
 
-        let max_compute_workgroups_per_dimension = gl
-            .get_parameter_indexed_i32(glow::MAX_COMPUTE_WORK_GROUP_COUNT, 0)
-            .min(gl.get_parameter_indexed_i32(glow::MAX_COMPUTE_WORK_GROUP_COUNT, 1))
-            .min(gl.get_parameter_indexed_i32(glow::MAX_COMPUTE_WORK_GROUP_COUNT, 2))
-            as u32;
+        let mut max_compute_workgroups = [0; 3];
+        for (index, max) in max_compute_workgroups.iter_mut().enumerate() {
+            *max = gl
+                .get_parameter_indexed_i32(glow::MAX_COMPUTE_WORK_GROUP_COUNT, index as u32)
+                .max(0) as u32;
+        }
+        if downlevel_flags.contains(wgt::DownlevelFlags::COMPUTE_SHADERS)
+            && max_compute_workgroups.contains(&0)
+        {
+            // Otherwise every dispatch would fail validation.
+            log::warn!("Max compute work group count is 0, using the minimum of 65535 required by GLES 3.1");
+            for max in max_compute_workgroups.iter_mut().filter(|max| **max == 0) {
+                *max = 65535;
+            }
+        }
+        let [max_compute_workgroups_x, max_compute_workgroups_y, max_compute_workgroups_z] =
+            max_compute_workgroups;
 
-        let limits = wgt::Limits {
+        let mut limits = wgt::Limits {
             max_texture_dimension_1d: max_texture_size,
             max_texture_dimension_2d: max_texture_size,
             max_texture_dimension_3d: max_texture_3d_size,
@@ -370,7 +573,7 @@ impl super::Adapter {
             } else {
                 !0
             },
-            max_push_constant_size: 0,
+            max_push_constant_size: super::MAX_PUSH_CONSTANT_SIZE as u32,
             min_uniform_buffer_offset_alignment,
             min_storage_buffer_offset_alignment,
             max_compute_workgroup_size_x: gl
@@ -382,7 +585,12 @@ impl super::Adapter {
             max_compute_workgroup_size_z: gl
                 .get_parameter_indexed_i32(glow::MAX_COMPUTE_WORK_GROUP_SIZE, 2)
                 as u32,
-            max_compute_workgroups_per_dimension,
+            max_compute_workgroups_per_dimension: max_compute_workgroups_x
+                .min(max_compute_workgroups_y)
+                .min(max_compute_workgroups_z),
+            max_compute_workgroups_x,
+            max_compute_workgroups_y,
+            max_compute_workgroups_z,
         };
 
         let mut workarounds = super::Workarounds::empty();
@@ -407,7 +615,32 @@ impl super::Adapter {
             workarounds.set(super::Workarounds::MESA_I915_SRGB_SHADER_CLEAR, true);
         }
 
-        let downlevel_defaults = wgt::DownlevelLimits {};
+        // Check for the Adreno storage block size bug. See
+        // [`super::Workarounds::ADRENO_SSBO_SIZE_CLAMP`].
+        if r.contains("adreno")
+            && limits.max_storage_buffer_binding_size > MIN_MAX_SHADER_STORAGE_BLOCK_SIZE
+        {
+            log::warn!(
+                "Detected Adreno driver. Clamping the storage buffer binding size from {} to {}.",
+                limits.max_storage_buffer_binding_size,
+                MIN_MAX_SHADER_STORAGE_BLOCK_SIZE
+            );
+            workarounds.set(super::Workarounds::ADRENO_SSBO_SIZE_CLAMP, true);
+            limits.max_storage_buffer_binding_size = MIN_MAX_SHADER_STORAGE_BLOCK_SIZE;
+        }
+
+        let downlevel_limits = wgt::DownlevelLimits {
+            max_storage_buffers_per_pipeline: if supports_storage {
+                gl.get_parameter_i32(glow::MAX_COMBINED_SHADER_STORAGE_BLOCKS) as u32
+            } else {
+                0
+            },
+            max_storage_textures_per_pipeline: if supports_storage {
+                gl.get_parameter_i32(glow::MAX_COMBINED_IMAGE_UNIFORMS) as u32
+            } else {
+                0
+            },
+        };
 
         // Drop the GL guard so we can move the context into AdapterShared
         // ( on WASM the gl handle is just a ref so we tell clippy to allow
@@ -422,6 +655,9 @@ impl super::Adapter {
                     private_caps,
                     workarounds,
                     shading_language_version,
+                    max_texture_max_anisotropy,
+                    max_label_length,
+                    max_uniform_buffers_per_shader_stage,
                 }),
             },
             info: Self::make_info(vendor, renderer),
@@ -430,17 +666,31 @@ impl super::Adapter {
                 limits,
                 downlevel: wgt::DownlevelCapabilities {
                     flags: downlevel_flags,
-                    limits: downlevel_defaults,
+                    limits: downlevel_limits,
                     shader_model: wgt::ShaderModel::Sm5,
                 },
                 alignments: crate::Alignments {
                     buffer_copy_offset: wgt::BufferSize::new(4).unwrap(),
                     buffer_copy_pitch: wgt::BufferSize::new(4).unwrap(),
                 },
+                // Render pass clears go through the shader clear program.
+                srgb_clear_needs_render_pass: workarounds
+                    .contains(super::Workarounds::MESA_I915_SRGB_SHADER_CLEAR),
             },
         })
     }
 
+    /// Maps the failed creation of a GL object to a device error.
+    ///
+    /// Creating objects also fails on a lost context, which unlike running
+    /// out of memory can't be recovered from.
+    unsafe fn object_creation_error(gl: &glow::Context) -> crate::DeviceError {
+        match gl.get_error() {
+            glow::CONTEXT_LOST | glow::INVALID_OPERATION => crate::DeviceError::Lost,
+            _ => crate::DeviceError::OutOfMemory,
+        }
+    }
+
     unsafe fn create_shader_clear_program(
         gl: &glow::Context,
     ) -> (glow::Program, glow::UniformLocation) {
@@ -481,16 +731,39 @@ impl crate::Adapter<super::Api> for super::Adapter {
         gl.pixel_store_i32(glow::PACK_ALIGNMENT, 1);
         let main_vao = gl
             .create_vertex_array()
-            .map_err(|_| crate::DeviceError::OutOfMemory)?;
+            .map_err(|_| Self::object_creation_error(gl))?;
         gl.bind_vertex_array(Some(main_vao));
 
+        if self
+            .shared
+            .private_caps
+            .contains(super::PrivateCapabilities::CLIP_CONTROL)
+        {
+            // Use the [0, 1] depth range of the other backends. The Y-flip done by
+            // `glsl::WriterFlags::ADJUST_COORDINATE_SPACE` otherwise is done by the
+            // upper left origin instead, see `Device::create_pipeline_layout`.
+            let clip_control = self.shared.context.clip_control().unwrap();
+            clip_control(glow::UPPER_LEFT, glow::ZERO_TO_ONE);
+        }
+
         let zero_buffer = gl
             .create_buffer()
-            .map_err(|_| crate::DeviceError::OutOfMemory)?;
+            .map_err(|_| Self::object_creation_error(gl))?;
         gl.bind_buffer(glow::COPY_READ_BUFFER, Some(zero_buffer));
         let zeroes = vec![0u8; super::ZERO_BUFFER_SIZE];
         gl.buffer_data_u8_slice(glow::COPY_READ_BUFFER, &zeroes, glow::STATIC_DRAW);
 
+        let push_constant_buffer = gl
+            .create_buffer()
+            .map_err(|_| Self::object_creation_error(gl))?;
+        gl.bind_buffer(glow::UNIFORM_BUFFER, Some(push_constant_buffer));
+        gl.buffer_data_size(
+            glow::UNIFORM_BUFFER,
+            super::MAX_PUSH_CONSTANT_SIZE as i32,
+            glow::DYNAMIC_DRAW,
+        );
+        gl.bind_buffer(glow::UNIFORM_BUFFER, None);
+
         // Compile the shader program we use for doing manual clears to work around Mesa fastclear
         // bug.
         let (shader_clear_program, shader_clear_program_color_uniform_location) =
@@ -508,13 +781,14 @@ impl crate::Adapter<super::Api> for super::Adapter {
                 features,
                 draw_fbo: gl
                     .create_framebuffer()
-                    .map_err(|_| crate::DeviceError::OutOfMemory)?,
+                    .map_err(|_| Self::object_creation_error(gl))?,
                 copy_fbo: gl
                     .create_framebuffer()
-                    .map_err(|_| crate::DeviceError::OutOfMemory)?,
+                    .map_err(|_| Self::object_creation_error(gl))?,
                 shader_clear_program,
                 shader_clear_program_color_uniform_location,
                 zero_buffer,
+                push_constant_buffer,
                 temp_query_results: Vec::new(),
                 draw_buffer_count: 1,
                 current_index_buffer: None,
@@ -532,6 +806,13 @@ impl crate::Adapter<super::Api> for super::Adapter {
         // "TEXTURE IMAGE LOADS AND STORES" of GLES-3.2 spec.
         let unfiltered_color = Tfc::SAMPLED | Tfc::COLOR_ATTACHMENT;
         let filtered_color = unfiltered_color | Tfc::SAMPLED_LINEAR | Tfc::COLOR_ATTACHMENT_BLEND;
+        let compressed = |cap| {
+            if self.shared.private_caps.contains(cap) {
+                Tfc::SAMPLED | Tfc::SAMPLED_LINEAR
+            } else {
+                Tfc::empty()
+            }
+        };
         match format {
             Tf::R8Unorm | Tf::R8Snorm => filtered_color,
             Tf::R8Uint | Tf::R8Sint | Tf::R16Uint | Tf::R16Sint => unfiltered_color,
@@ -541,7 +822,10 @@ impl crate::Adapter<super::Api> for super::Adapter {
             }
             Tf::R32Float => unfiltered_color,
             Tf::Rg16Uint | Tf::Rg16Sint => unfiltered_color,
-            Tf::Rg16Float | Tf::Rgba8Unorm | Tf::Rgba8UnormSrgb => filtered_color | Tfc::STORAGE,
+            Tf::Rg16Float => filtered_color,
+            Tf::Rgba8Unorm => filtered_color | Tfc::STORAGE,
+            // sRGB formats are not in the image unit format table, so they can't be storage.
+            Tf::Rgba8UnormSrgb => filtered_color,
             Tf::Bgra8UnormSrgb | Tf::Rgba8Snorm | Tf::Bgra8Unorm => filtered_color,
             Tf::Rgba8Uint | Tf::Rgba8Sint => unfiltered_color | Tfc::STORAGE,
             Tf::Rgb10a2Unorm | Tf::Rg11b10Float => filtered_color,
@@ -551,24 +835,20 @@ impl crate::Adapter<super::Api> for super::Adapter {
             Tf::Rgba16Float => filtered_color | Tfc::STORAGE,
             Tf::Rgba32Uint | Tf::Rgba32Sint => unfiltered_color | Tfc::STORAGE,
             Tf::Rgba32Float => unfiltered_color | Tfc::STORAGE,
-            Tf::Depth32Float => Tfc::SAMPLED | Tfc::DEPTH_STENCIL_ATTACHMENT,
+            Tf::Depth32Float => {
+                if self
+                    .shared
+                    .private_caps
+                    .contains(super::PrivateCapabilities::DEPTH32F_ATTACHMENT)
+                {
+                    Tfc::SAMPLED | Tfc::DEPTH_STENCIL_ATTACHMENT
+                } else {
+                    Tfc::SAMPLED
+                }
+            }
             Tf::Depth24Plus => Tfc::SAMPLED | Tfc::DEPTH_STENCIL_ATTACHMENT,
             Tf::Depth24PlusStencil8 => Tfc::SAMPLED | Tfc::DEPTH_STENCIL_ATTACHMENT,
             Tf::Rgb9e5Ufloat
-            | Tf::Bc1RgbaUnorm
-            | Tf::Bc1RgbaUnormSrgb
-            | Tf::Bc2RgbaUnorm
-            | Tf::Bc2RgbaUnormSrgb
-            | Tf::Bc3RgbaUnorm
-            | Tf::Bc3RgbaUnormSrgb
-            | Tf::Bc4RUnorm
-            | Tf::Bc4RSnorm
-            | Tf::Bc5RgUnorm
-            | Tf::Bc5RgSnorm
-            | Tf::Bc6hRgbSfloat
-            | Tf::Bc6hRgbUfloat
-            | Tf::Bc7RgbaUnorm
-            | Tf::Bc7RgbaUnormSrgb
             | Tf::Etc2Rgb8Unorm
             | Tf::Etc2Rgb8UnormSrgb
             | Tf::Etc2Rgb8A1Unorm
@@ -578,8 +858,22 @@ impl crate::Adapter<super::Api> for super::Adapter {
             | Tf::EacR11Unorm
             | Tf::EacR11Snorm
             | Tf::EacRg11Unorm
-            | Tf::EacRg11Snorm
-            | Tf::Astc4x4RgbaUnorm
+            | Tf::EacRg11Snorm => Tfc::SAMPLED | Tfc::SAMPLED_LINEAR,
+            Tf::Bc1RgbaUnorm
+            | Tf::Bc1RgbaUnormSrgb
+            | Tf::Bc2RgbaUnorm
+            | Tf::Bc2RgbaUnormSrgb
+            | Tf::Bc3RgbaUnorm
+            | Tf::Bc3RgbaUnormSrgb => {
+                compressed(super::PrivateCapabilities::TEXTURE_COMPRESSION_S3TC)
+            }
+            Tf::Bc4RUnorm | Tf::Bc4RSnorm | Tf::Bc5RgUnorm | Tf::Bc5RgSnorm => {
+                compressed(super::PrivateCapabilities::TEXTURE_COMPRESSION_RGTC)
+            }
+            Tf::Bc6hRgbSfloat | Tf::Bc6hRgbUfloat | Tf::Bc7RgbaUnorm | Tf::Bc7RgbaUnormSrgb => {
+                compressed(super::PrivateCapabilities::TEXTURE_COMPRESSION_BPTC)
+            }
+            Tf::Astc4x4RgbaUnorm
             | Tf::Astc4x4RgbaUnormSrgb
             | Tf::Astc5x4RgbaUnorm
             | Tf::Astc5x4RgbaUnormSrgb
@@ -606,7 +900,9 @@ impl crate::Adapter<super::Api> for super::Adapter {
             | Tf::Astc12x10RgbaUnorm
             | Tf::Astc12x10RgbaUnormSrgb
             | Tf::Astc12x12RgbaUnorm
-            | Tf::Astc12x12RgbaUnormSrgb => Tfc::SAMPLED | Tfc::SAMPLED_LINEAR,
+            | Tf::Astc12x12RgbaUnormSrgb => {
+                compressed(super::PrivateCapabilities::TEXTURE_COMPRESSION_ASTC_LDR)
+            }
         }
     }
 
@@ -659,6 +955,7 @@ unsafe impl Send for super::Adapter {}
 #[cfg(test)]
 mod tests {
     use super::super::Adapter;
+    use super::VertexStorageSupport;
 
     #[test]
     fn test_version_parse() {
@@ -675,6 +972,14 @@ mod tests {
         );
         assert_eq!(Adapter::parse_version("GLSL ES 1.1"), Ok((1, 1)));
         assert_eq!(Adapter::parse_version("OpenGL ES GLSL ES 3.20"), Ok((3, 2)));
+        assert_eq!(
+            Adapter::parse_version("OpenGL ES 3.2.0 build-foo"),
+            Ok((3, 2))
+        );
+        assert_eq!(Adapter::parse_version("OpenGL ES 3.1.2.3"), Ok((3, 1)));
+        assert_eq!(Adapter::parse_version("OpenGL ES 3.0-build"), Ok((3, 0)));
+        assert_eq!(Adapter::parse_version("OpenGL ES 3.2Mesa 21.2"), Ok((3, 2)));
+        assert_eq!(Adapter::parse_version("OpenGL ES 3.x"), error);
         assert_eq!(
             // WebGL 2.0 should parse as OpenGL ES 3.0
             Adapter::parse_version("WebGL 2.0 (OpenGL ES 3.0 Chromium)"),
@@ -685,4 +990,75 @@ mod tests {
             Ok((3, 0))
         );
     }
+
+    #[test]
+    fn test_make_info_device_type() {
+        let device_type = |vendor: &str, renderer: &str| {
+            Adapter::make_info(vendor.to_string(), renderer.to_string()).device_type
+        };
+        assert_eq!(
+            device_type("Mesa/X.org", "llvmpipe (LLVM 12.0.0, 256 bits)"),
+            wgt::DeviceType::Cpu
+        );
+        assert_eq!(device_type("Mesa/X.org", "softpipe"), wgt::DeviceType::Cpu);
+        assert_eq!(
+            device_type(
+                "Google Inc.",
+                "ANGLE (Microsoft, Microsoft Basic Render Driver Direct3D11 vs_5_0 ps_5_0, D3D11)"
+            ),
+            wgt::DeviceType::Cpu
+        );
+        assert_eq!(
+            device_type("Mesa/X.org", "virgl (Intel(R) UHD Graphics 620)"),
+            wgt::DeviceType::VirtualGpu
+        );
+        assert_eq!(
+            device_type("Red Hat", "virtio_gpu"),
+            wgt::DeviceType::VirtualGpu
+        );
+        assert_eq!(
+            device_type("Intel", "Mesa Intel(R) UHD Graphics 620 (KBL GT2)"),
+            wgt::DeviceType::IntegratedGpu
+        );
+        assert_eq!(
+            device_type("NVIDIA Corporation", "NVIDIA GeForce GTX 1080/PCIe/SSE2"),
+            wgt::DeviceType::DiscreteGpu
+        );
+    }
+
+    #[test]
+    fn test_vertex_storage_support() {
+        let support = |readable, writable| VertexStorageSupport {
+            vertex_storage_readable: readable,
+            vertex_storage_writable: writable,
+        };
+        // no storage buffers at all
+        assert_eq!(
+            Adapter::vertex_storage_support(0, 0, 0),
+            support(false, false)
+        );
+        assert_eq!(
+            Adapter::vertex_storage_support(0, 4, 4),
+            support(false, false)
+        );
+        // no vertex storage
+        assert_eq!(
+            Adapter::vertex_storage_support(1 << 16, 0, 0),
+            support(false, false)
+        );
+        // RPi4: zero vertex SSBOs, but some vertex SSTOs
+        assert_eq!(
+            Adapter::vertex_storage_support(1 << 16, 0, 4),
+            support(true, false)
+        );
+        // vertex SSBOs, but no vertex SSTOs
+        assert_eq!(
+            Adapter::vertex_storage_support(1 << 16, 4, 0),
+            support(true, false)
+        );
+        assert_eq!(
+            Adapter::vertex_storage_support(1 << 16, 4, 4),
+            support(true, true)
+        );
+    }
 }
@@ -43,6 +43,14 @@ impl super::CommandBuffer {
         self.data_bytes.extend(marker.as_bytes());
         start..self.data_bytes.len() as u32
     }
+
+    fn add_push_constant_data(&mut self, data: &[u32]) -> Range<u32> {
+        let start = self.data_bytes.len() as u32;
+        for word in data {
+            self.data_bytes.extend_from_slice(&word.to_ne_bytes());
+        }
+        start..self.data_bytes.len() as u32
+    }
 }
 
 impl super::CommandEncoder {
@@ -245,6 +253,20 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         });
     }
 
+    unsafe fn fill_buffer(
+        &mut self,
+        buffer: &super::Buffer,
+        range: crate::MemoryRange,
+        value: u32,
+    ) {
+        self.cmd_buffer.commands.push(C::FillBuffer {
+            dst: buffer.inner.clone(),
+            dst_target: buffer.target,
+            range,
+            value,
+        });
+    }
+
     unsafe fn copy_buffer_to_buffer<T>(
         &mut self,
         src: &super::Buffer,
@@ -347,8 +369,9 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
     unsafe fn end_query(&mut self, set: &super::QuerySet, _index: u32) {
         self.cmd_buffer.commands.push(C::EndQuery(set.target));
     }
-    unsafe fn write_timestamp(&mut self, _set: &super::QuerySet, _index: u32) {
-        unimplemented!()
+    unsafe fn write_timestamp(&mut self, set: &super::QuerySet, index: u32) {
+        let query = set.queries[index as usize];
+        self.cmd_buffer.commands.push(C::TimestampQuery(query));
     }
     unsafe fn reset_queries(&mut self, _set: &super::QuerySet, _range: Range<u32>) {
         //TODO: what do we do here?
@@ -368,6 +391,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         let query_range = start as u32..self.cmd_buffer.queries.len() as u32;
         self.cmd_buffer.commands.push(C::CopyQueryResults {
             query_range,
+            query_target: set.target,
             dst: buffer.inner.clone(),
             dst_target: buffer.target,
             dst_offset: offset,
@@ -583,19 +607,27 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
 
     unsafe fn set_push_constants(
         &mut self,
-        _layout: &super::PipelineLayout,
+        layout: &super::PipelineLayout,
         _stages: wgt::ShaderStages,
-        _offset: u32,
-        _data: &[u32],
+        offset: u32,
+        data: &[u32],
     ) {
-        unimplemented!()
+        let slot = layout
+            .push_constant_slot
+            .expect("pipeline layout has no push constant ranges");
+        let data = self.cmd_buffer.add_push_constant_data(data);
+        self.cmd_buffer.commands.push(C::SetPushConstants {
+            slot: slot as u32,
+            offset,
+            data,
+        });
     }
 
-    unsafe fn insert_debug_marker(&mut self, label: &str) {
+    unsafe fn insert_debug_marker(&mut self, label: &str, _color: u32) {
         let range = self.cmd_buffer.add_marker(label);
         self.cmd_buffer.commands.push(C::InsertDebugMarker(range));
     }
-    unsafe fn begin_debug_marker(&mut self, group_label: &str) {
+    unsafe fn begin_debug_marker(&mut self, group_label: &str, _color: u32) {
         let range = self.cmd_buffer.add_marker(group_label);
         self.cmd_buffer.commands.push(C::PushDebugGroup(range));
     }
@@ -911,4 +943,22 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
             indirect_offset: offset,
         });
     }
+    unsafe fn dispatch_indirect_count(
+        &mut self,
+        _buffer: &super::Buffer,
+        _offset: wgt::BufferAddress,
+        _count_buffer: &super::Buffer,
+        _count_offset: wgt::BufferAddress,
+        _max_count: u32,
+    ) {
+        unreachable!()
+    }
+    unsafe fn dispatch_conditional(
+        &mut self,
+        _predicate: &super::Buffer,
+        _predicate_offset: wgt::BufferAddress,
+        _count: [u32; 3],
+    ) {
+        unreachable!()
+    }
 }
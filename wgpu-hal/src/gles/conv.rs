@@ -209,7 +209,7 @@ pub fn map_address_mode(mode: wgt::AddressMode) -> u32 {
         wgt::AddressMode::MirrorRepeat => glow::MIRRORED_REPEAT,
         wgt::AddressMode::ClampToEdge => glow::CLAMP_TO_EDGE,
         wgt::AddressMode::ClampToBorder => glow::CLAMP_TO_BORDER,
-        //wgt::AddressMode::MirrorClamp => glow::MIRROR_CLAMP_TO_EDGE,
+        wgt::AddressMode::MirrorClampToEdge => glow::MIRROR_CLAMP_TO_EDGE,
     }
 }
 
@@ -245,6 +245,8 @@ pub(super) fn map_primitive_state(state: &wgt::PrimitiveState) -> super::Primiti
         //Note: we are flipping the front face, so that
         // the Y-flip in the generated GLSL keeps the same visibility.
         // See `naga::back::glsl::WriterFlags::ADJUST_COORDINATE_SPACE`.
+        // The upper left clip origin used with `PrivateCapabilities::CLIP_CONTROL`
+        // flips the window coordinates the same way.
         front_face: match state.front_face {
             wgt::FrontFace::Cw => glow::CCW,
             wgt::FrontFace::Ccw => glow::CW,
@@ -255,6 +257,7 @@ pub(super) fn map_primitive_state(state: &wgt::PrimitiveState) -> super::Primiti
             None => 0,
         },
         clamp_depth: state.clamp_depth,
+        conservative: state.conservative,
     }
 }
 
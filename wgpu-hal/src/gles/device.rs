@@ -79,7 +79,149 @@ impl CompilationContext<'_> {
     }
 }
 
+/// Returns the alignment and size of `ty` in the std140 layout of GLSL uniform blocks,
+/// or `None` if naga laid it out differently.
+fn std140_layout(
+    types: &naga::UniqueArena<naga::Type>,
+    constants: &naga::Arena<naga::Constant>,
+    ty: naga::Handle<naga::Type>,
+) -> Option<(u32, u32)> {
+    // All alignments are powers of two.
+    let round_up = |alignment: u32, value: u32| (value + alignment - 1) & !(alignment - 1);
+    let vector_alignment = |size: naga::VectorSize, width: naga::Bytes| match size {
+        naga::VectorSize::Bi => 2 * width as u32,
+        naga::VectorSize::Tri | naga::VectorSize::Quad => 4 * width as u32,
+    };
+
+    match types[ty].inner {
+        naga::TypeInner::Scalar { width, .. } | naga::TypeInner::Atomic { width, .. } => {
+            Some((width as u32, width as u32))
+        }
+        naga::TypeInner::Vector { size, width, .. } => {
+            Some((vector_alignment(size, width), size as u32 * width as u32))
+        }
+        naga::TypeInner::Matrix {
+            columns,
+            rows,
+            width,
+        } => {
+            // std140 pads the columns to 16 bytes, naga only to their alignment.
+            let column_stride = vector_alignment(rows, width);
+            if column_stride % 16 != 0 {
+                return None;
+            }
+            Some((column_stride, columns as u32 * column_stride))
+        }
+        naga::TypeInner::Array { base, size, stride } => {
+            let (base_alignment, base_size) = std140_layout(types, constants, base)?;
+            let alignment = round_up(16, base_alignment);
+            if stride != round_up(alignment, base_size) {
+                return None;
+            }
+            let count = match size {
+                naga::ArraySize::Constant(handle) => match constants[handle].inner {
+                    naga::ConstantInner::Scalar {
+                        value: naga::ScalarValue::Uint(count),
+                        ..
+                    } => count as u32,
+                    naga::ConstantInner::Scalar {
+                        value: naga::ScalarValue::Sint(count),
+                        ..
+                    } => count as u32,
+                    _ => return None,
+                },
+                naga::ArraySize::Dynamic => return None,
+            };
+            Some((alignment, count * stride))
+        }
+        naga::TypeInner::Struct { ref members, .. } => {
+            let mut alignment = 16;
+            let mut offset = 0;
+            for member in members {
+                let (member_alignment, member_size) = std140_layout(types, constants, member.ty)?;
+                offset = round_up(member_alignment, offset);
+                if member.offset != offset {
+                    return None;
+                }
+                offset += member_size;
+                alignment = alignment.max(member_alignment);
+            }
+            Some((alignment, round_up(alignment, offset)))
+        }
+        _ => None,
+    }
+}
+
 impl super::Device {
+    /// Returns the `(format, type)` pair preferred for reading back a subresource
+    /// of `texture` with `glReadPixels`, as reported by `GL_IMPLEMENTATION_COLOR_READ_FORMAT`
+    /// and `GL_IMPLEMENTATION_COLOR_READ_TYPE`.
+    ///
+    /// Apart from `GL_RGBA`/`GL_UNSIGNED_BYTE` for normalized formats, this is the only
+    /// pair GLES guarantees to support. Returns `None` if the texture can't be attached
+    /// to a framebuffer.
+    ///
+    /// # Safety
+    ///
+    /// - `texture` must have been created on this device, and not be destroyed yet.
+    pub unsafe fn texture_color_read_format(
+        &self,
+        texture: &super::Texture,
+        mip_level: u32,
+        array_layer: u32,
+    ) -> Option<(u32, u32)> {
+        let gl = &self.shared.context.lock();
+        let fbo = gl.create_framebuffer().ok()?;
+        gl.bind_framebuffer(glow::READ_FRAMEBUFFER, Some(fbo));
+        match texture.inner {
+            super::TextureInner::Renderbuffer { raw } => {
+                gl.framebuffer_renderbuffer(
+                    glow::READ_FRAMEBUFFER,
+                    glow::COLOR_ATTACHMENT0,
+                    glow::RENDERBUFFER,
+                    Some(raw),
+                );
+            }
+            super::TextureInner::Texture { raw, target } => {
+                if super::queue::is_layered_target(target) {
+                    gl.framebuffer_texture_layer(
+                        glow::READ_FRAMEBUFFER,
+                        glow::COLOR_ATTACHMENT0,
+                        Some(raw),
+                        mip_level as i32,
+                        array_layer as i32,
+                    );
+                } else {
+                    let target = if target == glow::TEXTURE_CUBE_MAP {
+                        super::queue::CUBEMAP_FACES[array_layer as usize]
+                    } else {
+                        target
+                    };
+                    gl.framebuffer_texture_2d(
+                        glow::READ_FRAMEBUFFER,
+                        glow::COLOR_ATTACHMENT0,
+                        target,
+                        Some(raw),
+                        mip_level as i32,
+                    );
+                }
+            }
+        }
+
+        let result =
+            if gl.check_framebuffer_status(glow::READ_FRAMEBUFFER) == glow::FRAMEBUFFER_COMPLETE {
+                Some((
+                    gl.get_parameter_i32(glow::IMPLEMENTATION_COLOR_READ_FORMAT) as u32,
+                    gl.get_parameter_i32(glow::IMPLEMENTATION_COLOR_READ_TYPE) as u32,
+                ))
+            } else {
+                None
+            };
+        gl.bind_framebuffer(glow::READ_FRAMEBUFFER, None);
+        gl.delete_framebuffer(fbo);
+        result
+    }
+
     unsafe fn compile_shader(
         gl: &glow::Context,
         shader: &str,
@@ -180,7 +322,8 @@ impl super::Device {
         #[cfg(not(target_arch = "wasm32"))]
         if let Some(label) = label {
             if gl.supports_debug() {
-                gl.object_label(glow::PROGRAM, mem::transmute(program), Some(label));
+                let label = super::truncate_label(label, self.shared.max_label_length);
+                gl.object_label(glow::PROGRAM, mem::transmute(program), Some(&label));
             }
         }
 
@@ -308,6 +451,7 @@ impl crate::Device<super::Api> for super::Device {
         gl.delete_framebuffer(queue.draw_fbo);
         gl.delete_framebuffer(queue.copy_fbo);
         gl.delete_buffer(queue.zero_buffer);
+        gl.delete_buffer(queue.push_constant_buffer);
     }
 
     unsafe fn create_buffer(
@@ -408,7 +552,8 @@ impl crate::Device<super::Api> for super::Device {
         #[cfg(not(target_arch = "wasm32"))]
         if let Some(label) = desc.label {
             if gl.supports_debug() {
-                gl.object_label(glow::BUFFER, mem::transmute(raw), Some(label));
+                let label = super::truncate_label(label, self.shared.max_label_length);
+                gl.object_label(glow::BUFFER, mem::transmute(raw), Some(&label));
             }
         }
 
@@ -528,7 +673,8 @@ impl crate::Device<super::Api> for super::Device {
             #[cfg(not(target_arch = "wasm32"))]
             if let Some(label) = desc.label {
                 if gl.supports_debug() {
-                    gl.object_label(glow::RENDERBUFFER, mem::transmute(raw), Some(label));
+                    let label = super::truncate_label(label, self.shared.max_label_length);
+                    gl.object_label(glow::RENDERBUFFER, mem::transmute(raw), Some(&label));
                 }
             }
 
@@ -608,7 +754,8 @@ impl crate::Device<super::Api> for super::Device {
             #[cfg(not(target_arch = "wasm32"))]
             if let Some(label) = desc.label {
                 if gl.supports_debug() {
-                    gl.object_label(glow::TEXTURE, mem::transmute(raw), Some(label));
+                    let label = super::truncate_label(label, self.shared.max_label_length);
+                    gl.object_label(glow::TEXTURE, mem::transmute(raw), Some(&label));
                 }
             }
 
@@ -723,8 +870,12 @@ impl crate::Device<super::Api> for super::Device {
             gl.sampler_parameter_f32(raw, glow::TEXTURE_MAX_LOD, range.end);
         }
 
-        //TODO: `desc.anisotropy_clamp` depends on the downlevel flag
-        // gl.sampler_parameter_f32(rawow::TEXTURE_MAX_ANISOTROPY, aniso as f32);
+        if let Some(clamp) = desc.anisotropy_clamp {
+            if self.shared.max_texture_max_anisotropy > 1.0 {
+                let aniso = (clamp.get() as f32).min(self.shared.max_texture_max_anisotropy);
+                gl.sampler_parameter_f32(raw, glow::TEXTURE_MAX_ANISOTROPY_EXT, aniso);
+            }
+        }
 
         //set_param_float(glow::TEXTURE_LOD_BIAS, info.lod_bias.0);
 
@@ -744,7 +895,8 @@ impl crate::Device<super::Api> for super::Device {
         #[cfg(not(target_arch = "wasm32"))]
         if let Some(label) = desc.label {
             if gl.supports_debug() {
-                gl.object_label(glow::SAMPLER, mem::transmute(raw), Some(label));
+                let label = super::truncate_label(label, self.shared.max_label_length);
+                gl.object_label(glow::SAMPLER, mem::transmute(raw), Some(&label));
             }
         }
 
@@ -790,7 +942,16 @@ impl crate::Device<super::Api> for super::Device {
         let mut num_uniform_buffers = 0u8;
         let mut num_storage_buffers = 0u8;
 
-        let mut writer_flags = glsl::WriterFlags::ADJUST_COORDINATE_SPACE;
+        let mut writer_flags = glsl::WriterFlags::empty();
+        // With clip control, both the Y-flip and the depth range are handled
+        // by the clip space configuration set in `Adapter::open`.
+        writer_flags.set(
+            glsl::WriterFlags::ADJUST_COORDINATE_SPACE,
+            !self
+                .shared
+                .private_caps
+                .contains(super::PrivateCapabilities::CLIP_CONTROL),
+        );
         writer_flags.set(
             glsl::WriterFlags::TEXTURE_SHADOW_LOD,
             self.shared
@@ -840,6 +1001,21 @@ impl crate::Device<super::Api> for super::Device {
             });
         }
 
+        // Push constants take the first uniform buffer slot after the bind groups.
+        let push_constant_slot = if desc.push_constant_ranges.is_empty() {
+            None
+        } else if num_uniform_buffers as u32 + 1 > self.shared.max_uniform_buffers_per_shader_stage
+        {
+            log::error!(
+                "No uniform block left for the push constants, the bind groups take all {}",
+                self.shared.max_uniform_buffers_per_shader_stage
+            );
+            return Err(crate::DeviceError::ResourceCreationFailed);
+        } else {
+            binding_map.insert(super::PUSH_CONSTANT_BINDING, num_uniform_buffers);
+            Some(num_uniform_buffers)
+        };
+
         Ok(super::PipelineLayout {
             group_infos: group_infos.into_boxed_slice(),
             naga_options: glsl::Options {
@@ -847,6 +1023,7 @@ impl crate::Device<super::Api> for super::Device {
                 writer_flags,
                 binding_map,
             },
+            push_constant_slot,
         })
     }
     unsafe fn destroy_pipeline_layout(&self, _pipeline_layout: super::PipelineLayout) {}
@@ -935,9 +1112,31 @@ impl crate::Device<super::Api> for super::Device {
                 crate::ShaderInput::SpirV(_) => {
                     panic!("`Features::SPIRV_SHADER_PASSTHROUGH` is not enabled")
                 }
-                crate::ShaderInput::Naga(naga) => naga,
+                crate::ShaderInput::Naga(mut naga) => {
+                    // GLSL has no push constants, so they are turned into a uniform block
+                    // that is backed by the push constant buffer of the queue. The block
+                    // gets the std140 layout, which has to match the offsets used to
+                    // upload the push constants.
+                    for (_, var) in naga.module.global_variables.iter_mut() {
+                        if var.class == naga::StorageClass::PushConstant {
+                            if std140_layout(&naga.module.types, &naga.module.constants, var.ty)
+                                .is_none()
+                            {
+                                return Err(crate::ShaderError::Compilation(format!(
+                                    "push constant {:?} doesn't have a std140 compatible layout",
+                                    var.name
+                                )));
+                            }
+                            var.class = naga::StorageClass::Uniform;
+                            var.binding = Some(super::PUSH_CONSTANT_BINDING);
+                        }
+                    }
+                    naga
+                }
             },
-            label: desc.label.map(|str| str.to_string()),
+            label: desc
+                .label
+                .map(|str| super::truncate_label(str, self.shared.max_label_length).into_owned()),
         })
     }
     unsafe fn destroy_shader_module(&self, _module: super::ShaderModule) {}
@@ -1049,7 +1248,8 @@ impl crate::Device<super::Api> for super::Device {
                 if let Some(label) = desc.label {
                     temp_string.clear();
                     let _ = write!(temp_string, "{}[{}]", label, i);
-                    gl.object_label(glow::QUERY, mem::transmute(query), Some(&temp_string));
+                    let label = super::truncate_label(&temp_string, self.shared.max_label_length);
+                    gl.object_label(glow::QUERY, mem::transmute(query), Some(&label));
                 }
             }
             queries.push(query);
@@ -1059,6 +1259,7 @@ impl crate::Device<super::Api> for super::Device {
             queries: queries.into_boxed_slice(),
             target: match desc.ty {
                 wgt::QueryType::Occlusion => glow::ANY_SAMPLES_PASSED,
+                wgt::QueryType::Timestamp => glow::TIMESTAMP,
                 _ => unimplemented!(),
             },
         })
@@ -1133,3 +1334,46 @@ impl crate::Device<super::Api> for super::Device {
 unsafe impl Sync for super::Device {}
 #[cfg(target_arch = "wasm32")]
 unsafe impl Send for super::Device {}
+
+#[cfg(test)]
+mod tests {
+    use super::std140_layout;
+
+    fn push_constant_layout(source: &str) -> Option<(u32, u32)> {
+        let module = naga::front::wgsl::parse_str(source).unwrap();
+        let (_, var) = module
+            .global_variables
+            .iter()
+            .find(|(_, var)| var.class == naga::StorageClass::PushConstant)
+            .unwrap();
+        std140_layout(&module.types, &module.constants, var.ty)
+    }
+
+    #[test]
+    fn std140_push_constants() {
+        // A scalar after a `vec3` fills its padding in both layouts.
+        assert_eq!(
+            push_constant_layout(
+                "[[block]] struct Pc { a: vec3<f32>; b: f32; c: vec4<f32>; };
+                var<push_constant> pc: Pc;"
+            ),
+            Some((16, 32))
+        );
+        // The array stride is 4 instead of 16.
+        assert_eq!(
+            push_constant_layout(
+                "[[block]] struct Pc { a: array<f32, 4>; };
+                var<push_constant> pc: Pc;"
+            ),
+            None
+        );
+        // The columns are 8 bytes apart instead of 16.
+        assert_eq!(
+            push_constant_layout(
+                "[[block]] struct Pc { a: mat2x2<f32>; };
+                var<push_constant> pc: Pc;"
+            ),
+            None
+        );
+    }
+}
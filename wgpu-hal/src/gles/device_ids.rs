@@ -0,0 +1,48 @@
+//! GL has no way to query the PCI device id of an adapter, so we look it up
+//! from the renderer string for a few common adapters.
+
+/// Known adapters, as `(vendor id, lowercase part of the renderer string, device id)`.
+const DEVICE_IDS: &[(usize, &str, usize)] = &[
+    // NVIDIA
+    (0x10DE, "geforce gtx 1050 ti", 0x1C82),
+    (0x10DE, "geforce gtx 1060 6gb", 0x1C03),
+    (0x10DE, "geforce gtx 1070", 0x1B81),
+    (0x10DE, "geforce gtx 1080", 0x1B80),
+    (0x10DE, "geforce gtx 1080 ti", 0x1B06),
+    (0x10DE, "geforce rtx 3060", 0x2503),
+    (0x10DE, "geforce rtx 3060 ti", 0x2486),
+    (0x10DE, "geforce rtx 3070", 0x2484),
+    (0x10DE, "geforce rtx 3080", 0x2206),
+    (0x10DE, "geforce rtx 3090", 0x2204),
+];
+
+/// Returns the device id of the adapter with the given vendor id and lowercase
+/// renderer string, or 0 if it is unknown.
+///
+/// If several entries match, the most specific one wins,
+/// e.g. "geforce gtx 1080 ti" over "geforce gtx 1080".
+pub(super) fn lookup(vendor_id: usize, renderer: &str) -> usize {
+    DEVICE_IDS
+        .iter()
+        .filter(|&&(vendor, name, _)| vendor == vendor_id && renderer.contains(name))
+        .max_by_key(|&&(_, name, _)| name.len())
+        .map_or(0, |&(_, _, device)| device)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lookup;
+
+    #[test]
+    fn test_device_id_lookup() {
+        assert_eq!(lookup(0x10DE, "nvidia geforce rtx 3080/pcie/sse2"), 0x2206);
+        assert_eq!(lookup(0x10DE, "nvidia geforce gtx 1080/pcie/sse2"), 0x1B80);
+        assert_eq!(
+            lookup(0x10DE, "nvidia geforce gtx 1080 ti/pcie/sse2"),
+            0x1B06
+        );
+        // wrong vendor
+        assert_eq!(lookup(0x1002, "nvidia geforce rtx 3080/pcie/sse2"), 0);
+        assert_eq!(lookup(0x10DE, "nvidia geforce rtx 9999/pcie/sse2"), 0);
+    }
+}
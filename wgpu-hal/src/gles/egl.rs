@@ -255,6 +255,10 @@ pub struct AdapterContext {
     egl_display: egl::Display,
     pub(super) egl_context: egl::Context,
     egl_pbuffer: Option<egl::Surface>,
+    clip_control: Option<super::ClipControlFn>,
+    polygon_offset_clamp: Option<super::PolygonOffsetClampFn>,
+    query_counter: Option<super::QueryCounterFn>,
+    get_query_object_u64: Option<super::GetQueryObjectU64Fn>,
 }
 
 unsafe impl Sync for AdapterContext {}
@@ -285,6 +289,26 @@ impl<'a> Drop for AdapterContextLock<'a> {
 }
 
 impl AdapterContext {
+    /// Returns `glClipControl` or `glClipControlEXT`, if the driver provides it.
+    pub(super) fn clip_control(&self) -> Option<super::ClipControlFn> {
+        self.clip_control
+    }
+
+    /// Returns `glPolygonOffsetClampEXT`, if the driver provides it.
+    pub(super) fn polygon_offset_clamp(&self) -> Option<super::PolygonOffsetClampFn> {
+        self.polygon_offset_clamp
+    }
+
+    /// Returns `glQueryCounter` or `glQueryCounterEXT`, if the driver provides it.
+    pub(super) fn query_counter(&self) -> Option<super::QueryCounterFn> {
+        self.query_counter
+    }
+
+    /// Returns `glGetQueryObjectui64v` or `glGetQueryObjectui64vEXT`, if the driver provides it.
+    pub(super) fn get_query_object_u64(&self) -> Option<super::GetQueryObjectU64Fn> {
+        self.get_query_object_u64
+    }
+
     /// Get's the [`glow::Context`] without waiting for a lock
     ///
     /// # Safety
@@ -702,6 +726,9 @@ impl crate::Instance<super::Api> for Instance {
             );
         }
 
+        // The debug output callback is installed on the context shared by the
+        // adapter and its device, so `Adapter::open` doesn't install it again.
+        // `supports_debug` covers both `GL_KHR_debug` and GLES 3.2.
         if self.flags.contains(crate::InstanceFlags::VALIDATION) && gl.supports_debug() {
             log::info!("Enabling GLES debug output");
             gl.enable(glow::DEBUG_OUTPUT);
@@ -719,6 +746,25 @@ impl crate::Instance<super::Api> for Instance {
             egl_display: inner.display,
             egl_context: inner.context,
             egl_pbuffer: inner.pbuffer,
+            clip_control: inner
+                .egl
+                .get_proc_address("glClipControl")
+                .or_else(|| inner.egl.get_proc_address("glClipControlEXT"))
+                .map(|p| std::mem::transmute::<_, super::ClipControlFn>(p)),
+            polygon_offset_clamp: inner
+                .egl
+                .get_proc_address("glPolygonOffsetClampEXT")
+                .map(|p| std::mem::transmute::<_, super::PolygonOffsetClampFn>(p)),
+            query_counter: inner
+                .egl
+                .get_proc_address("glQueryCounter")
+                .or_else(|| inner.egl.get_proc_address("glQueryCounterEXT"))
+                .map(|p| std::mem::transmute::<_, super::QueryCounterFn>(p)),
+            get_query_object_u64: inner
+                .egl
+                .get_proc_address("glGetQueryObjectui64v")
+                .or_else(|| inner.egl.get_proc_address("glGetQueryObjectui64vEXT"))
+                .map(|p| std::mem::transmute::<_, super::GetQueryObjectU64Fn>(p)),
         })
         .into_iter()
         .collect()
@@ -65,6 +65,7 @@ mod adapter;
 mod command;
 mod conv;
 mod device;
+mod device_ids;
 mod queue;
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -77,7 +78,7 @@ use arrayvec::ArrayVec;
 
 use glow::HasContext;
 
-use std::{ops::Range, sync::Arc};
+use std::{borrow::Cow, ops::Range, sync::Arc};
 
 #[derive(Clone)]
 pub struct Api;
@@ -88,6 +89,27 @@ const MAX_TEXTURE_SLOTS: usize = 16;
 const MAX_SAMPLERS: usize = 16;
 const MAX_VERTEX_ATTRIBUTES: usize = 16;
 const ZERO_BUFFER_SIZE: usize = 256 << 10;
+/// Size of the uniform buffer that push constants are emulated with.
+const MAX_PUSH_CONSTANT_SIZE: usize = 128;
+/// Binding that push constant blocks are moved to when a shader module is created.
+/// The pipeline layout maps it to the slot of [`Queue::push_constant_buffer`].
+const PUSH_CONSTANT_BINDING: naga::ResourceBinding = naga::ResourceBinding {
+    group: !0,
+    binding: 0,
+};
+
+/// `glClipControl` (or `glClipControlEXT`), which isn't exposed by glow.
+type ClipControlFn = unsafe extern "system" fn(origin: u32, depth: u32);
+/// `glPolygonOffsetClampEXT`, which isn't exposed by glow.
+type PolygonOffsetClampFn = unsafe extern "system" fn(factor: f32, units: f32, clamp: f32);
+/// `glQueryCounter` (or `glQueryCounterEXT`), which isn't exposed by glow.
+type QueryCounterFn = unsafe extern "system" fn(id: u32, target: u32);
+/// `glGetQueryObjectui64v` (or `glGetQueryObjectui64vEXT`), which isn't exposed by glow.
+type GetQueryObjectU64Fn = unsafe extern "system" fn(id: u32, pname: u32, params: *mut u64);
+
+// Not exposed by glow, see `GL_NV_conservative_raster` and `GL_INTEL_conservative_rasterization`.
+const GL_CONSERVATIVE_RASTERIZATION_NV: u32 = 0x9346;
+const GL_CONSERVATIVE_RASTERIZATION_INTEL: u32 = 0x83FE;
 
 impl crate::Api for Api {
     type Instance = Instance;
@@ -132,8 +154,28 @@ bitflags::bitflags! {
         /// Indicates that buffers used as `GL_ELEMENT_ARRAY_BUFFER` may be created / initialized / used
         /// as other targets, if not present they must not be mixed with other targets.
         const INDEX_BUFFER_ROLE_CHANGE = 1 << 5;
-        /// Indicates that the device supports disabling draw buffers
+        /// Indicates that the device supports setting the blend state of draw buffers
+        /// independently, including disabling blending for a single draw buffer.
         const CAN_DISABLE_DRAW_BUFFER = 1 << 6;
+        /// Conservative rasterization is provided by the Intel extension instead of the NV one.
+        const CONSERVATIVE_RASTER_INTEL = 1 << 7;
+        /// Supports `glPolygonOffsetClamp` via `GL_EXT_polygon_offset_clamp`.
+        const POLYGON_OFFSET_CLAMP = 1 << 8;
+        /// Supports `Depth32Float` as a depth attachment (`GL_DEPTH_COMPONENT32F` is core in ES 3.0).
+        const DEPTH32F_ATTACHMENT = 1 << 9;
+        /// Supports `glQueryCounter` with `GL_TIMESTAMP` and 64-bit query results via
+        /// `GL_EXT_disjoint_timer_query` or `GL_ARB_timer_query`.
+        const TIMESTAMP_QUERY = 1 << 10;
+        /// Supports BC1-3 textures via `GL_EXT_texture_compression_s3tc`.
+        const TEXTURE_COMPRESSION_S3TC = 1 << 11;
+        /// Supports BC4-5 textures via `GL_EXT_texture_compression_rgtc`.
+        const TEXTURE_COMPRESSION_RGTC = 1 << 12;
+        /// Supports BC6h-7 textures via `GL_EXT_texture_compression_bptc`.
+        const TEXTURE_COMPRESSION_BPTC = 1 << 13;
+        /// Supports ASTC LDR textures, core in ES 3.2.
+        const TEXTURE_COMPRESSION_ASTC_LDR = 1 << 14;
+        /// Supports setting the clip space origin and depth range via `glClipControl`.
+        const CLIP_CONTROL = 1 << 15;
     }
 }
 
@@ -149,6 +191,10 @@ bitflags::bitflags! {
         const MESA_I915_SRGB_SHADER_CLEAR = 1 << 0;
         /// Buffer map must emulated becuase it is not supported natively
         const EMULATE_BUFFER_MAP = 1 << 1;
+        /// Adreno drivers report a `GL_MAX_SHADER_STORAGE_BLOCK_SIZE` larger than the
+        /// storage buffer ranges they can actually bind, so the limit is clamped to the
+        /// minimum required by GLES 3.1.
+        const ADRENO_SSBO_SIZE_CLAMP = 1 << 2;
     }
 }
 
@@ -179,6 +225,31 @@ struct AdapterShared {
     private_caps: PrivateCapabilities,
     workarounds: Workarounds,
     shading_language_version: naga::back::glsl::Version,
+    /// Value of `GL_MAX_TEXTURE_MAX_ANISOTROPY`, or 1.0 if anisotropic filtering is unsupported.
+    max_texture_max_anisotropy: f32,
+    /// Value of `GL_MAX_LABEL_LENGTH`, or 0 if debug labels are unsupported.
+    max_label_length: usize,
+    /// Uniform blocks available to every shader stage, including the one
+    /// taken by the emulated push constants.
+    max_uniform_buffers_per_shader_stage: u32,
+}
+
+/// Shortens `label` to be accepted by `glObjectLabel` and the debug marker functions,
+/// which fail on strings that aren't shorter than `GL_MAX_LABEL_LENGTH`.
+fn truncate_label(label: &str, max_length: usize) -> Cow<'_, str> {
+    const ELLIPSIS: &str = "...";
+    if max_length == 0 || label.len() < max_length {
+        return Cow::Borrowed(label);
+    }
+    // Without room for a character before it, the ellipsis would replace the whole label.
+    let (mut end, suffix) = match max_length - 1 {
+        length if length > ELLIPSIS.len() => (length - ELLIPSIS.len(), ELLIPSIS),
+        length => (length, ""),
+    };
+    while !label.is_char_boundary(end) {
+        end -= 1;
+    }
+    Cow::Owned(format!("{}{}", &label[..end], suffix))
 }
 
 pub struct Adapter {
@@ -205,6 +276,8 @@ pub struct Queue {
     /// Keep a reasonably large buffer filled with zeroes, so that we can implement `ClearBuffer` of
     /// zeroes by copying from it.
     zero_buffer: glow::Buffer,
+    /// Uniform buffer backing the push constants, which GLES doesn't have.
+    push_constant_buffer: glow::Buffer,
     temp_query_results: Vec<u64>,
     draw_buffer_count: u8,
     current_index_buffer: Option<glow::Buffer>,
@@ -307,10 +380,15 @@ struct BindGroupLayoutInfo {
 pub struct PipelineLayout {
     group_infos: Box<[BindGroupLayoutInfo]>,
     naga_options: naga::back::glsl::Options,
+    /// Uniform buffer slot of the push constants, if the layout has any.
+    push_constant_slot: Option<u8>,
 }
 
 impl PipelineLayout {
     fn get_slot(&self, br: &naga::ResourceBinding) -> u8 {
+        if *br == PUSH_CONSTANT_BINDING {
+            return self.push_constant_slot.unwrap();
+        }
         let group_info = &self.group_infos[br.group as usize];
         group_info.binding_to_slot[br.binding as usize]
     }
@@ -547,6 +625,7 @@ struct PrimitiveState {
     front_face: u32,
     cull_face: u32,
     clamp_depth: bool,
+    conservative: bool,
 }
 
 type InvalidatedAttachments = ArrayVec<u32, { crate::MAX_COLOR_TARGETS + 2 }>;
@@ -588,6 +667,12 @@ enum Command {
         dst_target: BindTarget,
         range: crate::MemoryRange,
     },
+    FillBuffer {
+        dst: BufferInner,
+        dst_target: BindTarget,
+        range: crate::MemoryRange,
+        value: u32,
+    },
     CopyBufferToBuffer {
         src: BufferInner,
         src_target: BindTarget,
@@ -623,8 +708,11 @@ enum Command {
     SetIndexBuffer(glow::Buffer),
     BeginQuery(glow::Query, BindTarget),
     EndQuery(BindTarget),
+    TimestampQuery(glow::Query),
     CopyQueryResults {
         query_range: Range<u32>,
+        /// Target of the query set, timestamps need 64-bit results.
+        query_target: BindTarget,
         dst: BufferInner,
         dst_target: BindTarget,
         dst_offset: wgt::BufferAddress,
@@ -706,6 +794,12 @@ enum Command {
         slot: u32,
         binding: ImageBinding,
     },
+    /// Upload `data` to the push constant buffer at `offset`, and bind it to `slot`.
+    SetPushConstants {
+        slot: u32,
+        offset: u32,
+        data: Range<u32>,
+    },
     InsertDebugMarker(Range<u32>),
     PushDebugGroup(Range<u32>),
     PopDebugGroup,
@@ -728,3 +822,26 @@ pub struct CommandEncoder {
     state: command::State,
     private_caps: PrivateCapabilities,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::truncate_label;
+
+    #[test]
+    fn test_truncate_label() {
+        assert_eq!(truncate_label("label", 0), "label");
+        assert_eq!(truncate_label("label", 6), "label");
+        assert_eq!(truncate_label("long label", 8), "long...");
+        // Multi-byte characters are never split.
+        assert_eq!(truncate_label("ab\u{e9}cdef", 7), "ab...");
+    }
+
+    #[test]
+    fn test_truncate_short_max_label_length() {
+        assert_eq!(truncate_label("label", 5), "l...");
+        assert_eq!(truncate_label("label", 4), "lab");
+        assert_eq!(truncate_label("label", 2), "l");
+        assert_eq!(truncate_label("label", 1), "");
+        assert_eq!(truncate_label("\u{e9}label", 3), "\u{e9}");
+    }
+}
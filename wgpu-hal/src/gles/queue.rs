@@ -6,7 +6,7 @@ use std::{mem, slice, sync::Arc};
 #[cfg(not(target_arch = "wasm32"))]
 const DEBUG_ID: u32 = 0;
 
-const CUBEMAP_FACES: [u32; 6] = [
+pub(super) const CUBEMAP_FACES: [u32; 6] = [
     glow::TEXTURE_CUBE_MAP_POSITIVE_X,
     glow::TEXTURE_CUBE_MAP_NEGATIVE_X,
     glow::TEXTURE_CUBE_MAP_POSITIVE_Y,
@@ -20,14 +20,39 @@ fn extract_marker<'a>(data: &'a [u8], range: &std::ops::Range<u32>) -> &'a str {
     std::str::from_utf8(&data[range.start as usize..range.end as usize]).unwrap()
 }
 
-fn is_layered_target(target: super::BindTarget) -> bool {
+pub(super) fn is_layered_target(target: super::BindTarget) -> bool {
     match target {
         glow::TEXTURE_2D_ARRAY | glow::TEXTURE_3D | glow::TEXTURE_CUBE_MAP_ARRAY => true,
         _ => false,
     }
 }
 
+/// Returns the `factor` and `units` arguments of `glPolygonOffset` for `bias`.
+/// The factor is scaled by the depth slope, the units are the constant part.
+fn polygon_offset_args(bias: &wgt::DepthBiasState) -> (f32, f32) {
+    (bias.slope_scale, bias.constant as f32)
+}
+
 impl super::Queue {
+    /// Reads a 64-bit query result, glow only provides 32-bit ones.
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe fn get_query_result_u64(context: &super::AdapterContext, query: glow::Query) -> u64 {
+        let get_query_object_u64 = context.get_query_object_u64().unwrap();
+        let mut result = 0;
+        get_query_object_u64(
+            mem::transmute::<glow::Query, u32>(query),
+            glow::QUERY_RESULT,
+            &mut result,
+        );
+        result
+    }
+
+    /// Only used for timestamps, which the web doesn't support.
+    #[cfg(target_arch = "wasm32")]
+    unsafe fn get_query_result_u64(_context: &super::AdapterContext, _query: glow::Query) -> u64 {
+        unreachable!()
+    }
+
     /// Performs a manual shader clear, used as a workaround for a clearing bug on mesa
     unsafe fn perform_shader_clear(&self, gl: &glow::Context, draw_buffer: u32, color: [f32; 4]) {
         gl.use_program(Some(self.shader_clear_program));
@@ -51,9 +76,14 @@ impl super::Queue {
             .map(|i| glow::COLOR_ATTACHMENT0 + i)
             .collect::<ArrayVec<_, { crate::MAX_COLOR_TARGETS }>>();
         gl.draw_buffers(&indices);
-        #[cfg(not(target_arch = "wasm32"))]
-        for draw_buffer in 0..self.draw_buffer_count as u32 {
-            gl.disable_draw_buffer(glow::BLEND, draw_buffer);
+        if self
+            .shared
+            .private_caps
+            .contains(super::PrivateCapabilities::CAN_DISABLE_DRAW_BUFFER)
+        {
+            for draw_buffer in 0..self.draw_buffer_count as u32 {
+                gl.disable_draw_buffer(glow::BLEND, draw_buffer);
+            }
         }
     }
 
@@ -69,6 +99,24 @@ impl super::Queue {
         if self.features.contains(wgt::Features::DEPTH_CLAMPING) {
             gl.disable(glow::DEPTH_CLAMP);
         }
+        if self
+            .features
+            .contains(wgt::Features::CONSERVATIVE_RASTERIZATION)
+        {
+            gl.disable(self.conservative_raster_cap());
+        }
+    }
+
+    fn conservative_raster_cap(&self) -> u32 {
+        if self
+            .shared
+            .private_caps
+            .contains(super::PrivateCapabilities::CONSERVATIVE_RASTER_INTEL)
+        {
+            super::GL_CONSERVATIVE_RASTERIZATION_INTEL
+        } else {
+            super::GL_CONSERVATIVE_RASTERIZATION_NV
+        }
     }
 
     unsafe fn set_attachment(
@@ -116,7 +164,7 @@ impl super::Queue {
         &mut self,
         gl: &glow::Context,
         command: &C,
-        #[cfg_attr(target_arch = "wasm32", allow(unused))] data_bytes: &[u8],
+        data_bytes: &[u8],
         queries: &[glow::Query],
     ) {
         match *command {
@@ -227,6 +275,44 @@ impl super::Queue {
                         .fill(0);
                 }
             },
+            C::FillBuffer {
+                ref dst,
+                dst_target,
+                ref range,
+                value,
+            } => {
+                let size = (range.end - range.start).min(super::ZERO_BUFFER_SIZE as u64);
+                let pattern = value
+                    .to_ne_bytes()
+                    .iter()
+                    .cycle()
+                    .take(size as usize)
+                    .cloned()
+                    .collect::<Vec<u8>>();
+                match *dst {
+                    super::BufferInner::Buffer(buffer) => {
+                        gl.bind_buffer(dst_target, Some(buffer));
+                        let mut dst_offset = range.start;
+                        while dst_offset < range.end {
+                            let size = (range.end - dst_offset).min(pattern.len() as u64);
+                            gl.buffer_sub_data_u8_slice(
+                                dst_target,
+                                dst_offset as i32,
+                                &pattern[..size as usize],
+                            );
+                            dst_offset += size;
+                        }
+                    }
+                    super::BufferInner::Data(ref data) => {
+                        let mut data = data.lock().unwrap();
+                        let dst =
+                            &mut data.as_mut_slice()[range.start as usize..range.end as usize];
+                        for chunk in dst.chunks_mut(pattern.len()) {
+                            chunk.copy_from_slice(&pattern[..chunk.len()]);
+                        }
+                    }
+                }
+            }
             C::CopyBufferToBuffer {
                 ref src,
                 src_target,
@@ -605,16 +691,32 @@ impl super::Queue {
             C::EndQuery(target) => {
                 gl.end_query(target);
             }
+            C::TimestampQuery(query) => {
+                // Only recorded with `PrivateCapabilities::TIMESTAMP_QUERY`, which the web lacks.
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    let query_counter = self.shared.context.query_counter().unwrap();
+                    query_counter(mem::transmute::<glow::Query, u32>(query), glow::TIMESTAMP);
+                }
+                #[cfg(target_arch = "wasm32")]
+                let _ = query;
+            }
             C::CopyQueryResults {
                 ref query_range,
+                query_target,
                 ref dst,
                 dst_target,
                 dst_offset,
             } => {
                 self.temp_query_results.clear();
                 for &query in queries[query_range.start as usize..query_range.end as usize].iter() {
-                    let result = gl.get_query_parameter_u32(query, glow::QUERY_RESULT);
-                    self.temp_query_results.push(result as u64);
+                    let result = if query_target == glow::TIMESTAMP {
+                        // Nanosecond timestamps overflow 32 bits within seconds.
+                        Self::get_query_result_u64(&self.shared.context, query)
+                    } else {
+                        gl.get_query_parameter_u32(query, glow::QUERY_RESULT) as u64
+                    };
+                    self.temp_query_results.push(result);
                 }
                 let query_data = slice::from_raw_parts(
                     self.temp_query_results.as_ptr() as *const u8,
@@ -893,7 +995,15 @@ impl super::Queue {
             C::SetDepthBias(bias) => {
                 if bias.is_enabled() {
                     gl.enable(glow::POLYGON_OFFSET_FILL);
-                    gl.polygon_offset(bias.constant as f32, bias.slope_scale);
+                    // A non-zero clamp is only accepted with `DownlevelFlags::DEPTH_BIAS_CLAMP`,
+                    // which is reported when `glPolygonOffsetClampEXT` is available.
+                    let (factor, units) = polygon_offset_args(&bias);
+                    match self.shared.context.polygon_offset_clamp() {
+                        Some(polygon_offset_clamp) if bias.clamp != 0.0 => {
+                            polygon_offset_clamp(factor, units, bias.clamp)
+                        }
+                        _ => gl.polygon_offset(factor, units),
+                    }
                 } else {
                     gl.disable(glow::POLYGON_OFFSET_FILL);
                 }
@@ -928,6 +1038,17 @@ impl super::Queue {
                         gl.disable(glow::DEPTH_CLAMP);
                     }
                 }
+                if self
+                    .features
+                    .contains(wgt::Features::CONSERVATIVE_RASTERIZATION)
+                {
+                    let cap = self.conservative_raster_cap();
+                    if state.conservative {
+                        gl.enable(cap);
+                    } else {
+                        gl.disable(cap);
+                    }
+                }
             }
             C::SetBlendConstant(c) => {
                 gl.blend_color(c[0], c[1], c[2], c[3]);
@@ -1028,6 +1149,17 @@ impl super::Queue {
                     binding.format,
                 );
             }
+            C::SetPushConstants {
+                slot,
+                offset,
+                ref data,
+            } => {
+                let data = &data_bytes[data.start as usize..data.end as usize];
+                gl.bind_buffer(glow::UNIFORM_BUFFER, Some(self.push_constant_buffer));
+                gl.buffer_sub_data_u8_slice(glow::UNIFORM_BUFFER, offset as i32, data);
+                gl.bind_buffer(glow::UNIFORM_BUFFER, None);
+                gl.bind_buffer_base(glow::UNIFORM_BUFFER, slot, Some(self.push_constant_buffer));
+            }
             #[cfg(not(target_arch = "wasm32"))]
             C::InsertDebugMarker(ref range) => {
                 let marker = extract_marker(data_bytes, range);
@@ -1036,7 +1168,7 @@ impl super::Queue {
                     glow::DEBUG_TYPE_MARKER,
                     DEBUG_ID,
                     glow::DEBUG_SEVERITY_NOTIFICATION,
-                    marker,
+                    super::truncate_label(marker, self.shared.max_label_length),
                 );
             }
             #[cfg(target_arch = "wasm32")]
@@ -1046,7 +1178,11 @@ impl super::Queue {
                 #[cfg(not(target_arch = "wasm32"))]
                 let marker = extract_marker(data_bytes, range);
                 #[cfg(not(target_arch = "wasm32"))]
-                gl.push_debug_group(glow::DEBUG_SOURCE_APPLICATION, DEBUG_ID, marker);
+                gl.push_debug_group(
+                    glow::DEBUG_SOURCE_APPLICATION,
+                    DEBUG_ID,
+                    super::truncate_label(marker, self.shared.max_label_length),
+                );
             }
             C::PopDebugGroup => {
                 #[cfg(not(target_arch = "wasm32"))]
@@ -1068,7 +1204,11 @@ impl crate::Queue<super::Api> for super::Queue {
         for cmd_buf in command_buffers.iter() {
             #[cfg(not(target_arch = "wasm32"))]
             if let Some(ref label) = cmd_buf.label {
-                gl.push_debug_group(glow::DEBUG_SOURCE_APPLICATION, DEBUG_ID, label);
+                gl.push_debug_group(
+                    glow::DEBUG_SOURCE_APPLICATION,
+                    DEBUG_ID,
+                    super::truncate_label(label, shared.max_label_length),
+                );
             }
 
             for command in cmd_buf.commands.iter() {
@@ -1116,3 +1256,18 @@ impl crate::Queue<super::Api> for super::Queue {
 unsafe impl Sync for super::Queue {}
 #[cfg(target_arch = "wasm32")]
 unsafe impl Send for super::Queue {}
+
+#[cfg(test)]
+mod tests {
+    use super::polygon_offset_args;
+
+    #[test]
+    fn test_polygon_offset_args() {
+        let bias = wgt::DepthBiasState {
+            constant: 2,
+            slope_scale: 1.5,
+            clamp: 0.0,
+        };
+        assert_eq!(polygon_offset_args(&bias), (1.5, 2.0));
+    }
+}
@@ -17,6 +17,26 @@ impl AdapterContext {
     pub fn lock(&self) -> &glow::Context {
         &self.glow_context
     }
+
+    /// `EXT_clip_control` isn't exposed by glow on the web.
+    pub(super) fn clip_control(&self) -> Option<super::ClipControlFn> {
+        None
+    }
+
+    /// `EXT_polygon_offset_clamp` isn't exposed by glow on the web.
+    pub(super) fn polygon_offset_clamp(&self) -> Option<super::PolygonOffsetClampFn> {
+        None
+    }
+
+    /// `EXT_disjoint_timer_query` isn't exposed by glow on the web.
+    pub(super) fn query_counter(&self) -> Option<super::QueryCounterFn> {
+        None
+    }
+
+    /// `EXT_disjoint_timer_query` isn't exposed by glow on the web.
+    pub(super) fn get_query_object_u64(&self) -> Option<super::GetQueryObjectU64Fn> {
+        None
+    }
 }
 
 #[derive(Debug)]
@@ -117,6 +117,8 @@ pub enum DeviceError {
     OutOfMemory,
     #[error("device is lost")]
     Lost,
+    #[error("the resource doesn't fit into the implementation limits")]
+    ResourceCreationFailed,
 }
 
 #[derive(Clone, Debug, PartialEq, Error)]
@@ -369,6 +371,9 @@ pub trait CommandEncoder<A: Api>: Send + Sync {
 
     unsafe fn clear_buffer(&mut self, buffer: &A::Buffer, range: MemoryRange);
 
+    /// Fills the range with `value` repeated. The range has to be 4-byte aligned.
+    unsafe fn fill_buffer(&mut self, buffer: &A::Buffer, range: MemoryRange, value: u32);
+
     unsafe fn copy_buffer_to_buffer<T>(&mut self, src: &A::Buffer, dst: &A::Buffer, regions: T)
     where
         T: Iterator<Item = BufferCopy>;
@@ -426,8 +431,10 @@ pub trait CommandEncoder<A: Api>: Send + Sync {
         data: &[u32],
     );
 
-    unsafe fn insert_debug_marker(&mut self, label: &str);
-    unsafe fn begin_debug_marker(&mut self, group_label: &str);
+    /// `color` is packed as RGBA8 (`0xRRGGBBAA`), with 0 meaning no color.
+    /// Backends that can't show colored markers ignore it.
+    unsafe fn insert_debug_marker(&mut self, label: &str, color: u32);
+    unsafe fn begin_debug_marker(&mut self, group_label: &str, color: u32);
     unsafe fn end_debug_marker(&mut self);
 
     // queries
@@ -518,6 +525,23 @@ pub trait CommandEncoder<A: Api>: Send + Sync {
 
     unsafe fn dispatch(&mut self, count: [u32; 3]);
     unsafe fn dispatch_indirect(&mut self, buffer: &A::Buffer, offset: wgt::BufferAddress);
+    unsafe fn dispatch_indirect_count(
+        &mut self,
+        buffer: &A::Buffer,
+        offset: wgt::BufferAddress,
+        count_buffer: &A::Buffer,
+        count_offset: wgt::BufferAddress,
+        max_count: u32,
+    );
+    /// Dispatch `count` only if the `u32` at `predicate_offset` is non-zero.
+    ///
+    /// The `predicate` buffer has to be in the `BufferUses::PREDICATE` state.
+    unsafe fn dispatch_conditional(
+        &mut self,
+        predicate: &A::Buffer,
+        predicate_offset: wgt::BufferAddress,
+        count: [u32; 3],
+    );
 }
 
 bitflags!(
@@ -637,10 +661,12 @@ bitflags::bitflags! {
         const STORAGE_READ = 1 << 7;
         const STORAGE_WRITE = 1 << 8;
         const INDIRECT = 1 << 9;
+        /// The predicate of a conditional dispatch.
+        const PREDICATE = 1 << 10;
         /// The combination of usages that can be used together (read-only).
         const INCLUSIVE = Self::MAP_READ.bits | Self::COPY_SRC.bits |
             Self::INDEX.bits | Self::VERTEX.bits | Self::UNIFORM.bits |
-            Self::STORAGE_READ.bits | Self::INDIRECT.bits;
+            Self::STORAGE_READ.bits | Self::INDIRECT.bits | Self::PREDICATE.bits;
         /// The combination of exclusive usages (write-only and read-write).
         /// These usages may still show up with others, but can't automatically be combined.
         const EXCLUSIVE = Self::MAP_WRITE.bits | Self::COPY_DST.bits | Self::STORAGE_WRITE.bits;
@@ -696,6 +722,9 @@ pub struct Capabilities {
     pub limits: wgt::Limits,
     pub alignments: Alignments,
     pub downlevel: wgt::DownlevelCapabilities,
+    /// sRGB color textures have to be cleared with a render pass,
+    /// since filling them from a zero buffer gives wrong results.
+    pub srgb_clear_needs_render_pass: bool,
 }
 
 #[derive(Debug)]
@@ -951,12 +951,16 @@ impl super::PrivateCapabilities {
                 max_compute_workgroup_size_y: 256,
                 max_compute_workgroup_size_z: 64,
                 max_compute_workgroups_per_dimension: 0xFFFF,
+                max_compute_workgroups_x: 0xFFFF,
+                max_compute_workgroups_y: 0xFFFF,
+                max_compute_workgroups_z: 0xFFFF,
             },
             alignments: crate::Alignments {
                 buffer_copy_offset: wgt::BufferSize::new(self.buffer_alignment).unwrap(),
                 buffer_copy_pitch: wgt::BufferSize::new(4).unwrap(),
             },
             downlevel,
+            srgb_clear_needs_render_pass: false,
         }
     }
 
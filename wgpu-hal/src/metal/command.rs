@@ -103,6 +103,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
     unsafe fn discard_encoding(&mut self) {
         self.leave_blit();
         self.raw_cmd_buf = None;
+        self.fill_pattern_buffers.clear();
     }
     unsafe fn end_encoding(&mut self) -> Result<super::CommandBuffer, crate::DeviceError> {
         self.leave_blit();
@@ -114,7 +115,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
     where
         I: Iterator<Item = super::CommandBuffer>,
     {
-        //do nothing
+        self.fill_pattern_buffers.clear();
     }
 
     unsafe fn transition_buffers<'a, T>(&mut self, _barriers: T)
@@ -134,6 +135,44 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         encoder.fill_buffer(&buffer.raw, conv::map_range(&range), 0);
     }
 
+    unsafe fn fill_buffer(
+        &mut self,
+        buffer: &super::Buffer,
+        range: crate::MemoryRange,
+        value: u32,
+    ) {
+        let bytes = value.to_ne_bytes();
+        if bytes.iter().all(|&b| b == bytes[0]) {
+            let encoder = self.enter_blit();
+            encoder.fill_buffer(&buffer.raw, conv::map_range(&range), bytes[0]);
+            return;
+        }
+
+        // Metal can only fill with a single byte, so copy from a buffer holding the pattern.
+        // Command buffers don't retain their resources, so the encoder keeps
+        // the pattern buffer alive until `reset_all`.
+        let pattern_size = (range.end - range.start).min(super::FILL_PATTERN_SIZE);
+        let pattern = bytes
+            .iter()
+            .cycle()
+            .take(pattern_size as usize)
+            .cloned()
+            .collect::<Vec<u8>>();
+        let pattern_buffer = self.shared.device.lock().new_buffer_with_data(
+            pattern.as_ptr() as *const _,
+            pattern_size,
+            mtl::MTLResourceOptions::StorageModeShared,
+        );
+        let encoder = self.enter_blit();
+        let mut offset = range.start;
+        while offset < range.end {
+            let size = (range.end - offset).min(pattern_size);
+            encoder.copy_from_buffer(&pattern_buffer, 0, &buffer.raw, offset, size);
+            offset += size;
+        }
+        self.fill_pattern_buffers.push(pattern_buffer);
+    }
+
     unsafe fn copy_buffer_to_buffer<T>(
         &mut self,
         src: &super::Buffer,
@@ -582,10 +621,10 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         //TODO
     }
 
-    unsafe fn insert_debug_marker(&mut self, label: &str) {
+    unsafe fn insert_debug_marker(&mut self, label: &str, _color: u32) {
         self.enter_any().insert_debug_signpost(label);
     }
-    unsafe fn begin_debug_marker(&mut self, group_label: &str) {
+    unsafe fn begin_debug_marker(&mut self, group_label: &str, _color: u32) {
         self.enter_any().push_debug_group(group_label);
     }
     unsafe fn end_debug_marker(&mut self) {
@@ -896,4 +935,22 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         let encoder = self.state.compute.as_ref().unwrap();
         encoder.dispatch_thread_groups_indirect(&buffer.raw, offset, self.state.raw_wg_size);
     }
+    unsafe fn dispatch_indirect_count(
+        &mut self,
+        _buffer: &super::Buffer,
+        _offset: wgt::BufferAddress,
+        _count_buffer: &super::Buffer,
+        _count_offset: wgt::BufferAddress,
+        _max_count: u32,
+    ) {
+        unreachable!()
+    }
+    unsafe fn dispatch_conditional(
+        &mut self,
+        _predicate: &super::Buffer,
+        _predicate_offset: wgt::BufferAddress,
+        _count: [u32; 3],
+    ) {
+        unreachable!()
+    }
 }
@@ -426,6 +426,7 @@ impl crate::Device<super::Api> for super::Device {
             raw_cmd_buf: None,
             state: super::CommandState::default(),
             temp: super::Temp::default(),
+            fill_pattern_buffers: Vec::new(),
         })
     }
     unsafe fn destroy_command_encoder(&self, _encoder: super::CommandEncoder) {}
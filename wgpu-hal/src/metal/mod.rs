@@ -35,6 +35,9 @@ pub struct Api;
 
 type ResourceIndex = u32;
 
+/// Maximum size of the temporary buffer used by `fill_buffer` for multi-byte patterns.
+const FILL_PATTERN_SIZE: wgt::BufferAddress = 64 << 10;
+
 impl crate::Api for Api {
     type Instance = Instance;
     type Surface = Surface;
@@ -714,6 +717,8 @@ pub struct CommandEncoder {
     raw_cmd_buf: Option<mtl::CommandBuffer>,
     state: CommandState,
     temp: Temp,
+    /// Pattern buffers used by `fill_buffer`, released on `reset_all`.
+    fill_pattern_buffers: Vec<mtl::Buffer>,
 }
 
 unsafe impl Send for CommandEncoder {}
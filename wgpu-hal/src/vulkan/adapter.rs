@@ -22,6 +22,7 @@ pub struct PhysicalDeviceFeatures {
     timeline_semaphore: Option<vk::PhysicalDeviceTimelineSemaphoreFeaturesKHR>,
     image_robustness: Option<vk::PhysicalDeviceImageRobustnessFeaturesEXT>,
     robustness2: Option<vk::PhysicalDeviceRobustness2FeaturesEXT>,
+    conditional_rendering: Option<vk::PhysicalDeviceConditionalRenderingFeaturesEXT>,
 }
 
 // This is safe because the structs have `p_next: *mut c_void`, which we null out/never read.
@@ -44,6 +45,9 @@ impl PhysicalDeviceFeatures {
         if let Some(ref mut feature) = self.imageless_framebuffer {
             info = info.push_next(feature);
         }
+        if let Some(ref mut feature) = self.conditional_rendering {
+            info = info.push_next(feature);
+        }
         info
     }
 
@@ -85,7 +89,10 @@ impl PhysicalDeviceFeatures {
             core: vk::PhysicalDeviceFeatures::builder()
                 .robust_buffer_access(private_caps.robust_buffer_access)
                 .independent_blend(true)
-                .sample_rate_shading(true)
+                .sample_rate_shading(
+                    downlevel_flags.contains(wgt::DownlevelFlags::MULTISAMPLED_SHADING),
+                )
+                .depth_bias_clamp(downlevel_flags.contains(wgt::DownlevelFlags::DEPTH_BIAS_CLAMP))
                 .image_cube_array(
                     downlevel_flags.contains(wgt::DownlevelFlags::CUBE_ARRAY_TEXTURES),
                 )
@@ -272,6 +279,19 @@ impl PhysicalDeviceFeatures {
             } else {
                 None
             },
+            conditional_rendering: if enabled_extensions
+                .contains(&vk::ExtConditionalRenderingFn::name())
+            {
+                Some(
+                    vk::PhysicalDeviceConditionalRenderingFeaturesEXT::builder()
+                        .conditional_rendering(
+                            requested_features.contains(wgt::Features::CONDITIONAL_DISPATCH),
+                        )
+                        .build(),
+                )
+            } else {
+                None
+            },
         }
     }
 
@@ -295,6 +315,8 @@ impl PhysicalDeviceFeatures {
             Df::FRAGMENT_WRITABLE_STORAGE,
             self.core.fragment_stores_and_atomics != 0,
         );
+        dl_flags.set(Df::MULTISAMPLED_SHADING, self.core.sample_rate_shading != 0);
+        dl_flags.set(Df::DEPTH_BIAS_CLAMP, self.core.depth_bias_clamp != 0);
 
         //if self.core.dual_src_blend != 0
         features.set(F::MULTI_DRAW_INDIRECT, self.core.multi_draw_indirect != 0);
@@ -365,6 +387,12 @@ impl PhysicalDeviceFeatures {
             F::CONSERVATIVE_RASTERIZATION,
             caps.supports_extension(vk::ExtConservativeRasterizationFn::name()),
         );
+        if let Some(ref conditional_rendering) = self.conditional_rendering {
+            features.set(
+                F::CONDITIONAL_DISPATCH,
+                conditional_rendering.conditional_rendering != 0,
+            );
+        }
 
         let intel_windows = caps.properties.vendor_id == db::intel::VENDOR && cfg!(windows);
 
@@ -531,6 +559,10 @@ impl PhysicalDeviceCapabilities {
             extensions.push(vk::ExtConservativeRasterizationFn::name());
         }
 
+        if requested_features.contains(wgt::Features::CONDITIONAL_DISPATCH) {
+            extensions.push(vk::ExtConditionalRenderingFn::name());
+        }
+
         extensions
     }
 
@@ -592,9 +624,10 @@ impl PhysicalDeviceCapabilities {
         };
 
         let max_compute_workgroup_sizes = limits.max_compute_work_group_size;
-        let max_compute_workgroups_per_dimension = limits.max_compute_work_group_count[0]
-            .min(limits.max_compute_work_group_count[1])
-            .min(limits.max_compute_work_group_count[2]);
+        let max_compute_workgroups = limits.max_compute_work_group_count;
+        let max_compute_workgroups_per_dimension = max_compute_workgroups[0]
+            .min(max_compute_workgroups[1])
+            .min(max_compute_workgroups[2]);
 
         wgt::Limits {
             max_texture_dimension_1d: limits.max_image_dimension1_d,
@@ -627,6 +660,9 @@ impl PhysicalDeviceCapabilities {
             max_compute_workgroup_size_y: max_compute_workgroup_sizes[1],
             max_compute_workgroup_size_z: max_compute_workgroup_sizes[2],
             max_compute_workgroups_per_dimension,
+            max_compute_workgroups_x: max_compute_workgroups[0],
+            max_compute_workgroups_y: max_compute_workgroups[1],
+            max_compute_workgroups_z: max_compute_workgroups[2],
         }
     }
 
@@ -744,6 +780,13 @@ impl super::InstanceShared {
                 let mut_ref = features.robustness2.as_mut().unwrap();
                 mut_ref.p_next = mem::replace(&mut features2.p_next, mut_ref as *mut _ as *mut _);
             }
+            if capabilities.supports_extension(vk::ExtConditionalRenderingFn::name()) {
+                features.conditional_rendering =
+                    Some(vk::PhysicalDeviceConditionalRenderingFeaturesEXT::builder().build());
+
+                let mut_ref = features.conditional_rendering.as_mut().unwrap();
+                mut_ref.p_next = mem::replace(&mut features2.p_next, mut_ref as *mut _ as *mut _);
+            }
 
             unsafe {
                 get_device_properties.get_physical_device_features2_khr(phd, &mut features2);
@@ -769,6 +812,7 @@ impl super::InstanceShared {
             null_p_next(&mut features.timeline_semaphore);
             null_p_next(&mut features.image_robustness);
             null_p_next(&mut features.robustness2);
+            null_p_next(&mut features.conditional_rendering);
         }
 
         (capabilities, features)
@@ -892,9 +936,10 @@ impl super::Instance {
             alignments: phd_capabilities.to_hal_alignments(),
             downlevel: wgt::DownlevelCapabilities {
                 flags: downlevel_flags,
-                limits: wgt::DownlevelLimits {},
+                limits: wgt::DownlevelLimits::default(),
                 shader_model: wgt::ShaderModel::Sm5, //TODO?
             },
+            srgb_clear_needs_render_pass: false,
         };
 
         let adapter = super::Adapter {
@@ -1011,6 +1056,18 @@ impl super::Adapter {
         } else {
             None
         };
+        let conditional_rendering_fn =
+            if enabled_extensions.contains(&vk::ExtConditionalRenderingFn::name()) {
+                Some(vk::ExtConditionalRenderingFn::load(|name| {
+                    mem::transmute(
+                        self.instance
+                            .raw
+                            .get_device_proc_addr(raw_device.handle(), name.as_ptr()),
+                    )
+                }))
+            } else {
+                None
+            };
 
         let naga_options = {
             use naga::back::spv;
@@ -1077,6 +1134,7 @@ impl super::Adapter {
             extension_fns: super::DeviceExtensionFunctions {
                 draw_indirect_count: indirect_count_fn,
                 timeline_semaphore: timeline_semaphore_fn,
+                conditional_rendering: conditional_rendering_fn,
             },
             vendor_id: self.phd_capabilities.properties.vendor_id,
             timestamp_period: self.phd_capabilities.properties.limits.timestamp_period,
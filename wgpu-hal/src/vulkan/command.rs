@@ -194,6 +194,21 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         );
     }
 
+    unsafe fn fill_buffer(
+        &mut self,
+        buffer: &super::Buffer,
+        range: crate::MemoryRange,
+        value: u32,
+    ) {
+        self.device.raw.cmd_fill_buffer(
+            self.active,
+            buffer.raw,
+            range.start,
+            range.end - range.start,
+            value,
+        );
+    }
+
     unsafe fn copy_buffer_to_buffer<T>(
         &mut self,
         src: &super::Buffer,
@@ -437,7 +452,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         }
 
         if let Some(label) = desc.label {
-            self.begin_debug_marker(label);
+            self.begin_debug_marker(label, 0);
             self.rpass_debug_marker_active = true;
         }
 
@@ -494,17 +509,23 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         );
     }
 
-    unsafe fn insert_debug_marker(&mut self, label: &str) {
+    unsafe fn insert_debug_marker(&mut self, label: &str, color: u32) {
         if let Some(ext) = self.device.debug_messenger() {
             let cstr = self.temp.make_c_str(label);
-            let vk_label = vk::DebugUtilsLabelEXT::builder().label_name(cstr).build();
+            let vk_label = vk::DebugUtilsLabelEXT::builder()
+                .label_name(cstr)
+                .color(conv::map_marker_color(color))
+                .build();
             ext.cmd_insert_debug_utils_label(self.active, &vk_label);
         }
     }
-    unsafe fn begin_debug_marker(&mut self, group_label: &str) {
+    unsafe fn begin_debug_marker(&mut self, group_label: &str, color: u32) {
         if let Some(ext) = self.device.debug_messenger() {
             let cstr = self.temp.make_c_str(group_label);
-            let vk_label = vk::DebugUtilsLabelEXT::builder().label_name(cstr).build();
+            let vk_label = vk::DebugUtilsLabelEXT::builder()
+                .label_name(cstr)
+                .color(conv::map_marker_color(color))
+                .build();
             ext.cmd_begin_debug_utils_label(self.active, &vk_label);
         }
     }
@@ -722,7 +743,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
     unsafe fn begin_compute_pass(&mut self, desc: &crate::ComputePassDescriptor) {
         self.bind_point = vk::PipelineBindPoint::COMPUTE;
         if let Some(label) = desc.label {
-            self.begin_debug_marker(label);
+            self.begin_debug_marker(label, 0);
             self.rpass_debug_marker_active = true;
         }
     }
@@ -751,6 +772,38 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
             .raw
             .cmd_dispatch_indirect(self.active, buffer.raw, offset)
     }
+    unsafe fn dispatch_indirect_count(
+        &mut self,
+        _buffer: &super::Buffer,
+        _offset: wgt::BufferAddress,
+        _count_buffer: &super::Buffer,
+        _count_offset: wgt::BufferAddress,
+        _max_count: u32,
+    ) {
+        unreachable!()
+    }
+    unsafe fn dispatch_conditional(
+        &mut self,
+        predicate: &super::Buffer,
+        predicate_offset: wgt::BufferAddress,
+        count: [u32; 3],
+    ) {
+        let conditional_rendering = self
+            .device
+            .extension_fns
+            .conditional_rendering
+            .as_ref()
+            .expect("Feature `CONDITIONAL_DISPATCH` not enabled");
+
+        let info = vk::ConditionalRenderingBeginInfoEXT::builder()
+            .buffer(predicate.raw)
+            .offset(predicate_offset);
+        conditional_rendering.cmd_begin_conditional_rendering_ext(self.active, &*info);
+        self.device
+            .raw
+            .cmd_dispatch(self.active, count[0], count[1], count[2]);
+        conditional_rendering.cmd_end_conditional_rendering_ext(self.active);
+    }
 }
 
 #[test]
@@ -439,6 +439,9 @@ pub fn map_buffer_usage(usage: crate::BufferUses) -> vk::BufferUsageFlags {
     if usage.contains(crate::BufferUses::INDIRECT) {
         flags |= vk::BufferUsageFlags::INDIRECT_BUFFER;
     }
+    if usage.contains(crate::BufferUses::PREDICATE) {
+        flags |= vk::BufferUsageFlags::CONDITIONAL_RENDERING_EXT;
+    }
     flags
 }
 
@@ -491,6 +494,10 @@ pub fn map_buffer_usage_to_barrier(
         stages |= vk::PipelineStageFlags::DRAW_INDIRECT;
         access |= vk::AccessFlags::INDIRECT_COMMAND_READ;
     }
+    if usage.contains(crate::BufferUses::PREDICATE) {
+        stages |= vk::PipelineStageFlags::CONDITIONAL_RENDERING_EXT;
+        access |= vk::AccessFlags::CONDITIONAL_RENDERING_READ_EXT;
+    }
 
     (stages, access)
 }
@@ -583,7 +590,7 @@ pub fn map_address_mode(mode: wgt::AddressMode) -> vk::SamplerAddressMode {
         wgt::AddressMode::Repeat => vk::SamplerAddressMode::REPEAT,
         wgt::AddressMode::MirrorRepeat => vk::SamplerAddressMode::MIRRORED_REPEAT,
         wgt::AddressMode::ClampToBorder => vk::SamplerAddressMode::CLAMP_TO_BORDER,
-        //wgt::AddressMode::MirrorClamp => vk::SamplerAddressMode::MIRROR_CLAMP_TO_EDGE,
+        wgt::AddressMode::MirrorClampToEdge => vk::SamplerAddressMode::MIRROR_CLAMP_TO_EDGE,
     }
 }
 
@@ -765,3 +772,14 @@ pub fn map_pipeline_statistics(
     }
     flags
 }
+
+/// Unpacks a `0xRRGGBBAA` marker color. All zeroes means no color to Vulkan as well.
+pub fn map_marker_color(color: u32) -> [f32; 4] {
+    let [r, g, b, a] = color.to_be_bytes();
+    [
+        r as f32 / 255.0,
+        g as f32 / 255.0,
+        b as f32 / 255.0,
+        a as f32 / 255.0,
+    ]
+}
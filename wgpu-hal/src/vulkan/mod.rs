@@ -145,6 +145,7 @@ enum ExtensionFn<T> {
 struct DeviceExtensionFunctions {
     draw_indirect_count: Option<ExtensionFn<khr::DrawIndirectCount>>,
     timeline_semaphore: Option<ExtensionFn<khr::TimelineSemaphore>>,
+    conditional_rendering: Option<vk::ExtConditionalRenderingFn>,
 }
 
 /// Set of internal capabilities, which don't show up in the exposed
@@ -53,7 +53,10 @@ fn print_info_from_adapter(adapter: &wgpu::Adapter, idx: usize) {
         max_compute_workgroup_size_x,
         max_compute_workgroup_size_y,
         max_compute_workgroup_size_z,
-        max_compute_workgroups_per_dimension
+        max_compute_workgroups_per_dimension,
+        max_compute_workgroups_x,
+        max_compute_workgroups_y,
+        max_compute_workgroups_z,
     } = limits;
     println!("\t\tMax Texture Dimension 1d:                        {}", max_texture_dimension_1d);
     println!("\t\tMax Texture Dimension 2d:                        {}", max_texture_dimension_2d);
@@ -79,6 +82,9 @@ fn print_info_from_adapter(adapter: &wgpu::Adapter, idx: usize) {
     println!("\t\tMax Compute Workgroup Size Y:                    {}", max_compute_workgroup_size_y);
     println!("\t\tMax Compute Workgroup Size Z:                    {}", max_compute_workgroup_size_z);
     println!("\t\tMax Compute Workgroups Per Dimmension:           {}", max_compute_workgroups_per_dimension);
+    println!("\t\tMax Compute Workgroups X:                        {}", max_compute_workgroups_x);
+    println!("\t\tMax Compute Workgroups Y:                        {}", max_compute_workgroups_y);
+    println!("\t\tMax Compute Workgroups Z:                        {}", max_compute_workgroups_z);
     println!("\tDownlevel Properties:");
     let wgpu::DownlevelCapabilities {
         shader_model,
@@ -484,6 +484,7 @@ bitflags::bitflags! {
         ///
         /// Supported platforms:
         /// - Vulkan
+        /// - OpenGL (with `GL_NV_conservative_raster` or `GL_INTEL_conservative_rasterization`)
         ///
         /// This is a native only feature.
         const CONSERVATIVE_RASTERIZATION = 1 << 35;
@@ -526,6 +527,29 @@ bitflags::bitflags! {
         ///
         /// This is a native only feature.
         const SHADER_PRIMITIVE_INDEX = 1 << 39;
+        /// Allows the use of [`AddressMode::MirrorClampToEdge`].
+        ///
+        /// Supported platforms:
+        /// - OpenGL (with `GL_EXT_texture_mirror_clamp_to_edge` or `GL_ARB_texture_mirror_clamp_to_edge`)
+        ///
+        /// This is a native only feature.
+        const ADDRESS_MODE_MIRROR_CLAMP_TO_EDGE = 1 << 40;
+        /// Allows dispatching compute work indirectly multiple times, with the number
+        /// of dispatches read from a buffer, like [`Features::MULTI_DRAW_INDIRECT_COUNT`] does for draws.
+        ///
+        /// Supported platforms:
+        /// - DX12
+        ///
+        /// This is a native only feature.
+        const MULTI_DISPATCH_INDIRECT_COUNT = 1 << 41;
+        /// Allows compute dispatches that are skipped when a `u32` predicate in
+        /// a buffer is zero, without reading the predicate back on the CPU.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with VK_EXT_conditional_rendering)
+        ///
+        /// This is a native only feature.
+        const CONDITIONAL_DISPATCH = 1 << 42;
     }
 }
 
@@ -652,8 +676,19 @@ pub struct Limits {
     /// Defaults to 256.
     pub max_compute_workgroup_size_z: u32,
     /// The maximum value for each dimension of a `ComputePass::dispatch(x, y, z)` operation.
+    /// Adapters report the smallest of `max_compute_workgroups_x/y/z`, and dispatches
+    /// are checked against both this and the limit of each dimension.
     /// Defaults to 65535.
     pub max_compute_workgroups_per_dimension: u32,
+    /// The maximum value of the X dimension of a `ComputePass::dispatch(x, y, z)` operation.
+    /// Defaults to 65535.
+    pub max_compute_workgroups_x: u32,
+    /// The maximum value of the Y dimension of a `ComputePass::dispatch(x, y, z)` operation.
+    /// Defaults to 65535.
+    pub max_compute_workgroups_y: u32,
+    /// The maximum value of the Z dimension of a `ComputePass::dispatch(x, y, z)` operation.
+    /// Defaults to 65535.
+    pub max_compute_workgroups_z: u32,
 }
 
 impl Default for Limits {
@@ -683,6 +718,9 @@ impl Default for Limits {
             max_compute_workgroup_size_y: 256,
             max_compute_workgroup_size_z: 64,
             max_compute_workgroups_per_dimension: 65535,
+            max_compute_workgroups_x: 65535,
+            max_compute_workgroups_y: 65535,
+            max_compute_workgroups_z: 65535,
         }
     }
 }
@@ -715,6 +753,9 @@ impl Limits {
             max_compute_workgroup_size_y: 256,
             max_compute_workgroup_size_z: 64,
             max_compute_workgroups_per_dimension: 65535,
+            max_compute_workgroups_x: 65535,
+            max_compute_workgroups_y: 65535,
+            max_compute_workgroups_z: 65535,
         }
     }
 
@@ -761,13 +802,21 @@ impl Limits {
 /// Represents the sets of additional limits on an adapter,
 /// which take place when running on downlevel backends.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct DownlevelLimits {}
+pub struct DownlevelLimits {
+    /// Amount of storage buffers visible in all shader stages of a pipeline combined.
+    /// Defaults to `u32::MAX`, in which case only the per stage limit applies. Higher is "better".
+    pub max_storage_buffers_per_pipeline: u32,
+    /// Amount of storage textures visible in all shader stages of a pipeline combined.
+    /// Defaults to `u32::MAX`, in which case only the per stage limit applies. Higher is "better".
+    pub max_storage_textures_per_pipeline: u32,
+}
 
-#[allow(unknown_lints)] // derivable_impls is nightly only currently
-#[allow(clippy::derivable_impls)]
 impl Default for DownlevelLimits {
     fn default() -> Self {
-        DownlevelLimits {}
+        DownlevelLimits {
+            max_storage_buffers_per_pipeline: u32::MAX,
+            max_storage_textures_per_pipeline: u32::MAX,
+        }
     }
 }
 
@@ -850,6 +899,18 @@ bitflags::bitflags! {
 
         /// Supports storage buffers in fragment shaders.
         const FRAGMENT_STORAGE = 1 << 12;
+
+        /// Supports running the fragment shader once per sample of a multisampled target,
+        /// i.e. sample-rate shading.
+        const MULTISAMPLED_SHADING = 1 << 13;
+
+        /// Supports a non-zero `clamp` in [`DepthBiasState`].
+        const DEPTH_BIAS_CLAMP = 1 << 14;
+
+        /// Clip space depth is mapped to the [0, 1] depth range natively. Without it,
+        /// depth is remapped from [-1, 1] in the shaders, which loses the precision
+        /// near 0 that reversed-Z depth buffers rely on.
+        const NATIVE_DEPTH_RANGE = 1 << 15;
     }
 }
 
@@ -929,6 +990,11 @@ pub struct DeviceDescriptor<L> {
     /// Limits that the device should support. If any limit is "better" than the limit exposed by
     /// the adapter, creating a device will panic.
     pub limits: Limits,
+    /// Size of the buffer of zeros textures are cleared from. A larger buffer clears large
+    /// textures with fewer copies. Has to be a multiple of [`COPY_BYTES_PER_ROW_ALIGNMENT`].
+    /// Defaults to 512KiB if `None`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub zero_buffer_size: Option<BufferSize>,
 }
 
 impl<L> DeviceDescriptor<L> {
@@ -938,6 +1004,7 @@ impl<L> DeviceDescriptor<L> {
             label: fun(&self.label),
             features: self.features,
             limits: self.limits.clone(),
+            zero_buffer_size: self.zero_buffer_size,
         }
     }
 }
@@ -2919,6 +2986,12 @@ pub enum AddressMode {
     /// -0.25 -> border
     /// 1.25 -> border
     ClampToBorder = 3,
+    /// Mirror the texture once around zero, then clamp the value to the edge of the texture
+    /// Requires feature [`Features::ADDRESS_MODE_MIRROR_CLAMP_TO_EDGE`]
+    ///
+    /// -0.25 -> 0.25
+    /// 1.25 -> 1.0
+    MirrorClampToEdge = 4,
 }
 
 impl Default for AddressMode {
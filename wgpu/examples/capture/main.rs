@@ -39,6 +39,7 @@ async fn create_red_image_with_dimensions(
                 label: None,
                 features: wgpu::Features::empty(),
                 limits: wgpu::Limits::downlevel_defaults(),
+                zero_buffer_size: None,
             },
             None,
         )
@@ -175,6 +175,7 @@ async fn setup<E: Example>(title: &str) -> Setup {
                 label: None,
                 features: (optional_features & adapter_features) | required_features,
                 limits: needed_limits,
+                zero_buffer_size: None,
             },
             trace_dir.ok().as_ref().map(std::path::Path::new),
         )
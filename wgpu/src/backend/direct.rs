@@ -1903,6 +1903,8 @@ impl crate::Context for Context {
             encoder.id,
             &wgc::command::ComputePassDescriptor {
                 label: desc.label.map(Borrowed),
+                max_dispatch_count: None,
+                batch_barriers: false,
             },
         )
     }
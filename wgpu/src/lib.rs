@@ -2358,7 +2358,7 @@ impl CommandEncoder {
     /// # Panics
     ///
     /// - `CLEAR_COMMANDS` extension not enabled
-    /// - Buffer does not have `COPY_DST` usage.
+    /// - Buffer has neither `COPY_DST` nor `STORAGE` usage.
     /// - Range it out of bounds
     pub fn clear_buffer(
         &mut self,
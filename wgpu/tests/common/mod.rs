@@ -20,6 +20,7 @@ async fn initialize_device(
                 label: None,
                 features,
                 limits,
+                zero_buffer_size: None,
             },
             None,
         )
@@ -41,7 +42,7 @@ pub struct TestingContext {
 fn lowest_downlevel_properties() -> DownlevelCapabilities {
     DownlevelCapabilities {
         flags: wgt::DownlevelFlags::empty(),
-        limits: wgt::DownlevelLimits {},
+        limits: wgt::DownlevelLimits::default(),
         shader_model: wgt::ShaderModel::Sm2,
     }
 }
@@ -56,6 +57,7 @@ pub struct FailureCase {
 // This information determines if a test should run.
 pub struct TestParameters {
     pub required_features: Features,
+    pub required_limits: Limits,
     pub required_downlevel_properties: DownlevelCapabilities,
     // Backends where test should fail.
     pub failures: Vec<FailureCase>,
@@ -65,6 +67,7 @@ impl Default for TestParameters {
     fn default() -> Self {
         Self {
             required_features: Features::empty(),
+            required_limits: Limits::downlevel_defaults(),
             required_downlevel_properties: lowest_downlevel_properties(),
             failures: Vec::new(),
         }
@@ -93,6 +96,12 @@ impl TestParameters {
         self
     }
 
+    /// Set the limits this test requires.
+    pub fn limits(mut self, limits: Limits) -> Self {
+        self.required_limits = limits;
+        self
+    }
+
     pub fn downlevel_flags(mut self, downlevel_flags: DownlevelFlags) -> Self {
         self.required_downlevel_properties.flags |= downlevel_flags;
         self
@@ -156,7 +165,7 @@ pub fn initialize_test(parameters: TestParameters, test_function: impl FnOnce(Te
     ))
     .expect("could not find sutable adapter on the system");
 
-    let required_limits = Limits::downlevel_defaults();
+    let required_limits = parameters.required_limits.clone();
     let adapter_info = adapter.get_info();
     let adapter_lowercase_name = adapter_info.name.to_lowercase();
     let adapter_features = adapter.features();
@@ -0,0 +1,147 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+use crate::common::{initialize_test, TestParameters, TestingContext};
+
+const SHADER: &str = r#"
+[[block]]
+struct Output {
+    value: u32;
+};
+[[group(0), binding(0)]]
+var<storage, read_write> output: Output;
+
+[[stage(compute), workgroup_size(1)]]
+fn main() {
+    output.value = 42u;
+}
+"#;
+
+/// Returns the output buffer, which can be mapped for reading, the bind group
+/// binding it, and the pipeline running `SHADER`.
+fn output_pipeline(ctx: &TestingContext) -> (wgpu::Buffer, wgpu::BindGroup, wgpu::ComputePipeline) {
+    let shader = ctx
+        .device
+        .create_shader_module(&wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+
+    let pipeline = ctx
+        .device
+        .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: None,
+            layout: None,
+            module: &shader,
+            entry_point: "main",
+        });
+
+    let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 4,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let bg = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: None,
+        layout: &pipeline.get_bind_group_layout(0),
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: buffer.as_entire_binding(),
+        }],
+    });
+
+    (buffer, bg, pipeline)
+}
+
+// A pass that fails half-way must not leave the device unusable.
+#[test]
+fn compute_pass_error_does_not_wedge_device() {
+    initialize_test(
+        TestParameters::default()
+            .test_features()
+            .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS),
+        |ctx| {
+            let errored = Arc::new(AtomicBool::new(false));
+            let errored_handler = Arc::clone(&errored);
+            ctx.device.on_uncaptured_error(move |_| {
+                errored_handler.store(true, Ordering::SeqCst);
+            });
+
+            let (buffer, bg, pipeline) = output_pipeline(&ctx);
+
+            // Dispatching without a pipeline fails after the hal pass was opened.
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            {
+                let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+                cpass.set_bind_group(0, &bg, &[]);
+                cpass.dispatch(1, 1, 1);
+            }
+            drop(encoder);
+            assert!(errored.load(Ordering::SeqCst));
+
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            {
+                let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+                cpass.set_pipeline(&pipeline);
+                cpass.set_bind_group(0, &bg, &[]);
+                cpass.dispatch(1, 1, 1);
+            }
+            ctx.queue.submit(Some(encoder.finish()));
+
+            let slice = buffer.slice(..);
+            let _ = slice.map_async(wgpu::MapMode::Read);
+            ctx.device.poll(wgpu::Maintain::Wait);
+            let data: Vec<u32> = bytemuck::cast_slice(&*slice.get_mapped_range()).to_vec();
+
+            assert_eq!(data, [42]);
+        },
+    )
+}
+
+// Misaligned indirect offsets are rejected instead of being passed to the driver.
+#[test]
+fn dispatch_indirect_rejects_unaligned_offset() {
+    initialize_test(
+        TestParameters::default().downlevel_flags(
+            wgpu::DownlevelFlags::COMPUTE_SHADERS | wgpu::DownlevelFlags::INDIRECT_EXECUTION,
+        ),
+        |ctx| {
+            let errors = Arc::new(Mutex::new(Vec::new()));
+            let errors_handler = Arc::clone(&errors);
+            ctx.device.on_uncaptured_error(move |error| {
+                errors_handler.lock().unwrap().push(error.to_string());
+            });
+
+            let (_buffer, bg, pipeline) = output_pipeline(&ctx);
+            let indirect_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: 16,
+                usage: wgpu::BufferUsages::INDIRECT,
+                mapped_at_creation: false,
+            });
+
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            {
+                let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+                cpass.set_pipeline(&pipeline);
+                cpass.set_bind_group(0, &bg, &[]);
+                cpass.dispatch_indirect(&indirect_buffer, 2);
+            }
+            drop(encoder);
+
+            let errors = errors.lock().unwrap();
+            assert_eq!(errors.len(), 1);
+            assert!(errors[0].contains("indirect buffer offset 2 must be a multiple of 4"));
+        },
+    )
+}
@@ -0,0 +1,118 @@
+use std::num::NonZeroU64;
+
+use crate::common::{initialize_test, TestParameters};
+
+const SHADER: &str = r#"
+[[block]]
+struct PushConstants {
+    value: u32;
+};
+var<push_constant> pc: PushConstants;
+
+[[block]]
+struct Output {
+    value: u32;
+};
+[[group(0), binding(0)]]
+var<storage, read_write> output: Output;
+
+[[stage(compute), workgroup_size(1)]]
+fn main() {
+    output.value = pc.value;
+}
+"#;
+
+// Push constants set before the pipeline are applied once it is set.
+#[test]
+fn compute_push_constants_before_pipeline() {
+    initialize_test(
+        TestParameters::default()
+            .test_features()
+            .features(wgpu::Features::PUSH_CONSTANTS)
+            .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+            .limits(wgpu::Limits {
+                max_push_constant_size: 4,
+                ..wgpu::Limits::downlevel_defaults()
+            }),
+        |ctx| {
+            let shader = ctx
+                .device
+                .create_shader_module(&wgpu::ShaderModuleDescriptor {
+                    label: None,
+                    source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+                });
+
+            let bgl = ctx
+                .device
+                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                    label: None,
+                    entries: &[wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage { read_only: false },
+                            has_dynamic_offset: false,
+                            min_binding_size: NonZeroU64::new(4),
+                        },
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        count: None,
+                    }],
+                });
+
+            let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: 4,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::MAP_READ,
+                mapped_at_creation: false,
+            });
+
+            let bg = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: None,
+                layout: &bgl,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                }],
+            });
+
+            // Derived layouts have no push constant ranges, so the layout is explicit.
+            let ppl = ctx
+                .device
+                .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                    label: None,
+                    bind_group_layouts: &[&bgl],
+                    push_constant_ranges: &[wgpu::PushConstantRange {
+                        stages: wgpu::ShaderStages::COMPUTE,
+                        range: 0..4,
+                    }],
+                });
+
+            let pipeline = ctx
+                .device
+                .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                    label: None,
+                    layout: Some(&ppl),
+                    module: &shader,
+                    entry_point: "main",
+                });
+
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            {
+                let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+                cpass.set_push_constants(0, bytemuck::bytes_of(&42u32));
+                cpass.set_pipeline(&pipeline);
+                cpass.set_bind_group(0, &bg, &[]);
+                cpass.dispatch(1, 1, 1);
+            }
+            ctx.queue.submit(Some(encoder.finish()));
+
+            let slice = buffer.slice(..);
+            let _ = slice.map_async(wgpu::MapMode::Read);
+            ctx.device.poll(wgpu::Maintain::Wait);
+            let data: Vec<u32> = bytemuck::cast_slice(&*slice.get_mapped_range()).to_vec();
+
+            assert_eq!(data, [42]);
+        },
+    )
+}
@@ -2,8 +2,10 @@
 mod common;
 
 mod clear_texture;
+mod compute_pass_error;
 mod device;
 mod example_wgsl;
 mod instance;
+mod push_constants;
 mod vertex_indices;
 mod zero_init_texture_after_discard;